@@ -0,0 +1,108 @@
+#![feature(test)]
+
+//! Benchmarks for finding links in large inputs with few links, where most of the time is spent
+//! searching for trigger characters. Compare the results with and without the default `memchr`
+//! feature:
+//!
+//! ```sh
+//! cargo +nightly bench --features bench --bench triggers
+//! cargo +nightly bench --no-default-features --features bench --bench triggers
+//! ```
+
+extern crate test;
+
+use linkify::{LinkFinder, LinkKind};
+use test::Bencher;
+
+// A web server log, only the referrers are links
+fn log() -> String {
+    let mut log = String::new();
+    for i in 0..10_000 {
+        log.push_str(&format!(
+            "10.0.{}.{} - - [10/Oct/2020:13:55:36 +0200] \"GET /static/app-{}.js HTTP/1.1\" 200 \
+             2326 \"{}\" \"Mozilla/5.0 (X11; Linux x86_64; rv:81.0) Gecko/20100101 Firefox/81.0\"\n",
+            i / 256 % 256,
+            i % 256,
+            i,
+            if i % 100 == 0 {
+                "http://www.example.com/start.html"
+            } else {
+                "-"
+            },
+        ));
+    }
+    log
+}
+
+#[bench]
+fn log_urls(b: &mut Bencher) {
+    let input = log();
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Url]);
+    b.bytes = input.len() as u64;
+    b.iter(|| {
+        assert_eq!(finder.links(&input).count(), 100);
+    });
+}
+
+#[bench]
+fn log_urls_and_emails(b: &mut Bencher) {
+    let input = log();
+    let finder = LinkFinder::new();
+    b.bytes = input.len() as u64;
+    b.iter(|| {
+        assert_eq!(finder.links(&input).count(), 100);
+    });
+}
+
+#[bench]
+fn log_all_kinds(b: &mut Bencher) {
+    let input = log();
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[
+        LinkKind::Url,
+        LinkKind::Email,
+        LinkKind::Tel,
+        LinkKind::Hashtag,
+        LinkKind::Mention,
+    ]);
+    b.bytes = input.len() as u64;
+    b.iter(|| finder.links(&input).count());
+}
+
+// A mailbox with lots of punctuation but few email addresses
+fn mbox() -> String {
+    let mut mbox = String::new();
+    for i in 0..1_000 {
+        mbox.push_str(&format!(
+            "From: Sender {} <sender{}@example.org>\n\
+             Subject: Re: Notes, part {}...\n\n\
+             Thanks. See the notes below: items 1.2, 3.4 and 5.6 are done; the rest is not.\n\
+             Next steps: review, test, ship. Regards.\n\n",
+            i, i, i,
+        ));
+    }
+    mbox
+}
+
+#[bench]
+fn mbox_emails(b: &mut Bencher) {
+    let input = mbox();
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Email]);
+    b.bytes = input.len() as u64;
+    b.iter(|| {
+        assert_eq!(finder.links(&input).count(), 1_000);
+    });
+}
+
+#[bench]
+fn mbox_urls_and_emails(b: &mut Bencher) {
+    let input = mbox();
+    let mut finder = LinkFinder::new();
+    finder.url_must_have_scheme(false);
+    b.bytes = input.len() as u64;
+    b.iter(|| {
+        assert_eq!(finder.links(&input).count(), 1_000);
+    });
+}
@@ -44,3 +44,70 @@ fn heaps_of_links(b: &mut Bencher) {
         assert_eq!(links.count(), 12);
     });
 }
+
+#[bench]
+fn dots_without_scheme(b: &mut Bencher) {
+    let input = ".".repeat(10_000);
+    let mut link_finder = LinkFinder::new();
+    link_finder.url_must_have_scheme(false);
+    b.iter(|| {
+        assert_eq!(link_finder.links(&input).count(), 0);
+    });
+}
+
+#[bench]
+fn dashes_and_dots_without_scheme(b: &mut Bencher) {
+    let input = "-.".repeat(5_000);
+    let mut link_finder = LinkFinder::new();
+    link_finder.url_must_have_scheme(false);
+    b.iter(|| {
+        assert_eq!(link_finder.links(&input).count(), 0);
+    });
+}
+
+#[bench]
+fn labels_without_scheme(b: &mut Bencher) {
+    let input = "a.b.c.example.test ".repeat(1_000);
+    let mut link_finder = LinkFinder::new();
+    link_finder
+        .url_must_have_scheme(false)
+        .allowed_tlds(&["org"]);
+    b.iter(|| {
+        assert_eq!(link_finder.links(&input).count(), 0);
+    });
+}
+
+#[bench]
+fn long_run_of_labels_without_scheme(b: &mut Bencher) {
+    let input = "a.".repeat(5_000);
+    let mut link_finder = LinkFinder::new();
+    link_finder
+        .url_must_have_scheme(false)
+        .allowed_tlds(&["org"]);
+    b.iter(|| {
+        assert_eq!(link_finder.links(&input).count(), 0);
+    });
+}
+
+#[bench]
+fn long_run_of_numbers_without_scheme(b: &mut Bencher) {
+    let input = "1.".repeat(5_000);
+    let mut link_finder = LinkFinder::new();
+    link_finder.url_must_have_scheme(false);
+    b.iter(|| {
+        assert_eq!(link_finder.links(&input).count(), 0);
+    });
+}
+
+#[bench]
+fn long_ascii_text_with_links(b: &mut Bencher) {
+    let input = "Some text with https://www.example.com/path/to/page?query=value&other=1#fragment \
+                 and another link (see http://example.org/wiki/Link_(disambiguation)), then \
+                 www.example.net/a/b/c.html. "
+        .repeat(1_000);
+    let mut link_finder = LinkFinder::new();
+    link_finder.url_must_have_scheme(false);
+    b.iter(|| {
+        assert_eq!(link_finder.links(&input).count(), 3_000);
+    });
+}
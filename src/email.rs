@@ -1,28 +1,70 @@
-use std::ops::Range;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
 
+use crate::ip;
 use crate::scanner::Scanner;
+#[cfg(feature = "tlds")]
+use crate::tlds;
 
 /// Scan for email address starting from the trigger character "@".
 ///
-/// Based on RFC 6531, but also accepts invalid IDNs. Doesn't try to handle IP addresses in domain part or
-/// quoting in local part.
+/// Based on RFC 6531, but also accepts invalid IDNs. Only handles IP addresses in the domain part
+/// and quoting in local part if enabled.
+#[derive(Clone, Debug)]
 pub struct EmailScanner {
     pub domain_must_have_dot: bool,
+    /// Domains that are allowed without a dot, like `localhost`
+    pub allowed_hosts: Vec<String>,
+    pub max_length: Option<usize>,
+    /// Accept address literals like `[192.0.2.1]` or `[IPv6:2001:db8::1]` as the domain
+    pub ip_literal: bool,
+    /// Accept quoted local parts like `"john doe"`
+    pub quoted_local: bool,
+    /// Reject addresses if the top-level domain of the domain part is not known
+    #[cfg(feature = "tlds")]
+    pub require_valid_tld: bool,
 }
 
 impl Scanner for EmailScanner {
     fn scan(&self, s: &str, at: usize) -> Option<Range<usize>> {
-        if let Some(start) = self.find_start(&s[0..at]) {
-            let after = at + 1;
-            if let Some(end) = self.find_end(&s[after..]) {
-                let range = Range {
-                    start,
-                    end: after + end,
-                };
-                return Some(range);
+        let start = if self.quoted_local && s[..at].ends_with('"') {
+            self.find_quoted_start(&s[0..at])?
+        } else {
+            self.find_start(&s[0..at])?
+        };
+        let mailto = Self::is_mailto(&s[..start]);
+        let start = if mailto {
+            start - "mailto:".len()
+        } else {
+            start
+        };
+        let after = at + 1;
+        let max_end_length = match self.max_length {
+            Some(max_length) => Some(max_length.checked_sub(after - start)?),
+            None => None,
+        };
+        let end = if self.ip_literal && s[after..].starts_with('[') {
+            after + Self::find_ip_literal_end(&s[after..], max_end_length)?
+        } else {
+            let end = after + self.find_end(&s[after..], max_end_length)?;
+            #[cfg(feature = "tlds")]
+            {
+                let domain = &s[after..end];
+                if self.require_valid_tld && !Self::tld_valid(domain) && !self.host_allowed(domain)
+                {
+                    return None;
+                }
             }
-        }
-        None
+            end
+        };
+        let end = if mailto && s[end..].starts_with('?') {
+            let max_query_length = max_end_length.map(|max| max - (end - after));
+            end + Self::find_query_end(&s[end..], max_query_length)?
+        } else {
+            end
+        };
+        Some(Range { start, end })
     }
 }
 
@@ -43,18 +85,118 @@ impl EmailScanner {
             } else {
                 break;
             }
+            if let Some(max_length) = self.max_length {
+                // The "@" counts towards the length as well
+                if s.len() - i + 1 > max_length {
+                    return None;
+                }
+            }
         }
         first
     }
 
+    // See "Quoted-string" in RFC 5321, plus extensions in RFC 6531. The input ends with the
+    // closing quote.
+    fn find_quoted_start(&self, s: &str) -> Option<usize> {
+        let close = s.len() - 1;
+        let mut open = None;
+        for (i, c) in s[..close].char_indices().rev() {
+            if let Some(max_length) = self.max_length {
+                // The "@" counts towards the length as well
+                if s.len() - i + 1 > max_length {
+                    return None;
+                }
+            }
+            if c == '"' && Self::unescaped(&s[..i]) {
+                open = Some(i);
+                break;
+            }
+            if c.is_control() {
+                return None;
+            }
+        }
+        let open = open?;
+
+        // The quotes must not be empty or escaped
+        if open + 1 == close || !Self::unescaped(&s[..close]) {
+            return None;
+        }
+        // Quoting is for the whole local part, it can't be combined with atoms
+        if let Some(c) = s[..open].chars().next_back() {
+            if Self::local_atom_allowed(c) || c == '.' {
+                return None;
+            }
+        }
+        Some(open)
+    }
+
+    // Whether a character after `s` is not escaped, i.e. if there's an even number of backslashes
+    // at the end of `s`.
+    fn unescaped(s: &str) -> bool {
+        s.bytes().rev().take_while(|&b| b == b'\\').count() % 2 == 0
+    }
+
+    // Whether the address is part of a `mailto:` URI, see RFC 6068
+    fn is_mailto(s: &str) -> bool {
+        let len = "mailto:".len();
+        if s.len() < len || !s.as_bytes()[s.len() - len..].eq_ignore_ascii_case(b"mailto:") {
+            return false;
+        }
+        match s[..s.len() - len].chars().next_back() {
+            Some(c) => !c.is_alphanumeric(),
+            None => true,
+        }
+    }
+
+    // The end of the header fields of a `mailto:` URI like `?subject=Hi`, see "hfields" in
+    // RFC 6068. Like for URLs, trailing punctuation and unbalanced parentheses are not included.
+    // Returns `None` if it would be longer than `max_length`.
+    fn find_query_end(s: &str, max_length: Option<usize>) -> Option<usize> {
+        let mut round = 0;
+        let mut end = 0;
+        for (i, c) in s.char_indices() {
+            let can_be_last = match c {
+                '?' | '!' | '.' | ',' | ':' | ';' => false,
+                '(' => {
+                    round += 1;
+                    false
+                }
+                ')' => {
+                    round -= 1;
+                    if round < 0 {
+                        break;
+                    }
+                    true
+                }
+                '<' | '>' | '"' | '`' => break,
+                _ if c.is_whitespace() || c.is_control() => break,
+                _ => true,
+            };
+            if can_be_last {
+                end = i + c.len_utf8();
+                if max_length.map(|max| end > max).unwrap_or(false) {
+                    return None;
+                }
+            }
+        }
+        Some(end)
+    }
+
     // See "Domain" in RFC 5321, plus extension of "sub-domain" in RFC 6531
-    fn find_end(&self, s: &str) -> Option<usize> {
+    //
+    // Returns `None` if the domain would be longer than `max_length`.
+    pub fn find_end(&self, s: &str, max_length: Option<usize>) -> Option<usize> {
         let mut first_in_sub_domain = true;
         let mut can_end_sub_domain = false;
         let mut first_dot = None;
         let mut end = None;
 
         for (i, c) in s.char_indices() {
+            if let Some(max_length) = max_length {
+                if i + c.len_utf8() > max_length && (c == '-' || Self::sub_domain_allowed(c)) {
+                    return None;
+                }
+            }
             if first_in_sub_domain {
                 if Self::sub_domain_allowed(c) {
                     end = Some(i + c.len_utf8());
@@ -81,12 +223,45 @@ impl EmailScanner {
             }
         }
 
-        if let Some(end) = end {
-            if !self.domain_must_have_dot || first_dot.map(|d| d < end).unwrap_or(false) {
-                Some(end)
-            } else {
-                None
-            }
+        end.filter(|&end| {
+            !self.domain_must_have_dot
+                || first_dot.map(|d| d < end).unwrap_or(false)
+                || self.host_allowed(&s[..end])
+        })
+    }
+
+    fn host_allowed(&self, domain: &str) -> bool {
+        self.allowed_hosts
+            .iter()
+            .any(|host| host.eq_ignore_ascii_case(domain))
+    }
+
+    #[cfg(feature = "tlds")]
+    fn tld_valid(domain: &str) -> bool {
+        match domain.rfind('.') {
+            Some(i) => tlds::is_known(&domain[i + 1..]),
+            None => false,
+        }
+    }
+
+    // See "address-literal" in RFC 5321, only IPv4 and IPv6 addresses are supported. The input
+    // starts with the opening bracket.
+    fn find_ip_literal_end(s: &str, max_length: Option<usize>) -> Option<usize> {
+        let end = s.find(']')? + 1;
+        if max_length
+            .map(|max_length| end > max_length)
+            .unwrap_or(false)
+        {
+            return None;
+        }
+        let literal = &s[1..end - 1];
+        let valid = if literal.len() > 5 && literal.as_bytes()[..5].eq_ignore_ascii_case(b"IPv6:") {
+            ip::is_ipv6(&literal[5..])
+        } else {
+            ip::is_ipv4(literal)
+        };
+        if valid {
+            Some(end)
         } else {
             None
         }
@@ -117,7 +292,7 @@ impl EmailScanner {
             | '|'
             | '}'
             | '~' => true,
-            _ => c >= '\u{80}',
+            _ => Self::non_ascii_allowed(c),
         }
     }
 
@@ -126,7 +301,13 @@ impl EmailScanner {
     fn sub_domain_allowed(c: char) -> bool {
         match c {
             'a'..='z' | 'A'..='Z' | '0'..='9' => true,
-            _ => c >= '\u{80}',
+            _ => Self::non_ascii_allowed(c),
         }
     }
+
+    // See "UTF8-non-ascii" in RFC 6532, but not whitespace like U+00A0 (no-break space) or
+    // control characters, as they end an address in text
+    fn non_ascii_allowed(c: char) -> bool {
+        c >= '\u{80}' && !c.is_whitespace() && !c.is_control()
+    }
 }
@@ -0,0 +1,48 @@
+use core::ops::Range;
+
+use crate::scanner::Scanner;
+
+/// Scan for hashtags like "#rustlang" starting from the trigger character "#".
+///
+/// The tag consists of letters, digits and underscores (including Unicode letters), and needs at
+/// least one letter. The "#" needs to be at the start of the input or after whitespace.
+#[derive(Clone, Debug)]
+pub struct HashtagScanner {
+    pub max_length: Option<usize>,
+}
+
+impl Scanner for HashtagScanner {
+    fn scan(&self, s: &str, hash: usize) -> Option<Range<usize>> {
+        if let Some(c) = s[..hash].chars().next_back() {
+            if !c.is_whitespace() {
+                return None;
+            }
+        }
+
+        let after = hash + 1;
+        let mut end = None;
+        let mut has_letter = false;
+        for (i, c) in s[after..].char_indices() {
+            if c.is_alphabetic() {
+                has_letter = true;
+            } else if !(c.is_numeric() || c == '_') {
+                break;
+            }
+            let tag_end = after + i + c.len_utf8();
+            if self
+                .max_length
+                .map(|max_length| tag_end - hash > max_length)
+                .unwrap_or(false)
+            {
+                return None;
+            }
+            end = Some(tag_end);
+        }
+
+        if has_letter {
+            end.map(|end| Range { start: hash, end })
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,89 @@
+/// Check if `s` is the content of an IP literal host, like `::1` in `http://[::1]/`.
+///
+/// This is an IPv6 address as in "IP-literal" of RFC 3986, optionally followed by a zone
+/// identifier (RFC 6874) like `%25eth0` and a prefix length like `/64`.
+pub fn is_ipv6_literal(s: &str) -> bool {
+    let s = match s.rfind('/') {
+        Some(i) => {
+            let prefix = &s[i + 1..];
+            let valid = !prefix.is_empty()
+                && prefix.len() <= 3
+                && prefix.bytes().all(|b| b.is_ascii_digit())
+                && prefix.parse::<u8>().map(|p| p <= 128).unwrap_or(false);
+            if !valid {
+                return false;
+            }
+            &s[..i]
+        }
+        None => s,
+    };
+    let s = match s.find('%') {
+        Some(i) => {
+            if i + 1 == s.len() {
+                // Empty zone identifier
+                return false;
+            }
+            &s[..i]
+        }
+        None => s,
+    };
+    is_ipv6(s)
+}
+
+/// Check if `s` is an IPv6 address, see "IPv6address" in RFC 3986.
+pub fn is_ipv6(s: &str) -> bool {
+    match s.find("::") {
+        Some(i) => {
+            let (head, tail) = (&s[..i], &s[i + 2..]);
+            if tail.contains("::") {
+                return false;
+            }
+            match (count_groups(head, false), count_groups(tail, true)) {
+                // The `::` stands for at least one group of zeros
+                (Some(head), Some(tail)) => head + tail <= 7,
+                _ => false,
+            }
+        }
+        None => count_groups(s, true) == Some(8),
+    }
+}
+
+/// Check if `s` is an IPv4 address in dotted-decimal notation, like `192.168.0.1`.
+pub fn is_ipv4(s: &str) -> bool {
+    let mut count = 0;
+    for part in s.split('.') {
+        count += 1;
+        let valid = !part.is_empty()
+            && part.len() <= 3
+            && part.bytes().all(|b| b.is_ascii_digit())
+            && part.parse::<u8>().is_ok();
+        if !valid {
+            return false;
+        }
+    }
+    count == 4
+}
+
+// Count the number of 16-bit groups in colon-separated hex groups. An IPv4 address at the end
+// counts as two groups.
+fn count_groups(s: &str, allow_ipv4: bool) -> Option<usize> {
+    if s.is_empty() {
+        return Some(0);
+    }
+    let mut count = 0;
+    let mut parts = s.split(':').peekable();
+    while let Some(part) = parts.next() {
+        if allow_ipv4 && parts.peek().is_none() && part.contains('.') {
+            if !is_ipv4(part) {
+                return None;
+            }
+            count += 2;
+        } else if !part.is_empty() && part.len() <= 4 && part.bytes().all(|b| b.is_ascii_hexdigit())
+        {
+            count += 1;
+        } else {
+            return None;
+        }
+    }
+    Some(count)
+}
@@ -87,7 +87,7 @@
 //! This crates makes an effort to respect the various standards, namely:
 //!
 //! * [RFC 3986] and [RFC 3987] for URLs
-//! * [RFC 5321] and [RFC 6531] for emails (except IP addresses and quoting)
+//! * [RFC 5321] and [RFC 6531] for emails
 //!
 //! At the same time, it does not guarantee that the returned links are valid.
 //! If in doubt, it rather returns a link than skipping it.
@@ -104,17 +104,41 @@
 #![deny(warnings)]
 #![deny(missing_docs)]
 #![deny(missing_debug_implementations)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std as alloc;
 
 mod email;
 mod finder;
+mod hashtag;
+mod ip;
+mod mention;
+mod phone;
 mod scanner;
+mod stream;
+mod tel;
+#[cfg(feature = "tlds")]
+mod tlds;
 mod url;
 
+pub use crate::finder::Candidate;
 pub use crate::finder::Link;
 pub use crate::finder::LinkFinder;
 pub use crate::finder::LinkKind;
 pub use crate::finder::Links;
-pub use crate::finder::{Span, Spans};
+pub use crate::finder::LinksDetailed;
+pub use crate::finder::LinksWithContext;
+pub use crate::finder::LinksWithPosition;
+pub use crate::finder::OverlapPolicy;
+pub use crate::finder::OwnedLink;
+pub use crate::finder::Token;
+pub use crate::finder::{BytesLink, BytesLinks};
+pub use crate::finder::{Ranges, Span, Spans, TextSpans};
+pub use crate::scanner::Scanner;
+pub use crate::stream::LinkStream;
 
 #[cfg(doctest)]
 doc_comment::doctest!("../README.md");
@@ -0,0 +1,55 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::email::EmailScanner;
+use crate::scanner::Scanner;
+
+/// Scan for mentions like "@user" starting from the trigger character "@".
+///
+/// The user name consists of ASCII letters, digits and underscores. It can be followed by a
+/// domain, like "@user@example.org" for handles in the fediverse. The first "@" needs to be at
+/// the start of the input or after whitespace.
+#[derive(Clone, Debug)]
+pub struct MentionScanner {
+    pub max_length: Option<usize>,
+}
+
+impl Scanner for MentionScanner {
+    fn scan(&self, s: &str, at: usize) -> Option<Range<usize>> {
+        if let Some(c) = s[..at].chars().next_back() {
+            if !c.is_whitespace() {
+                return None;
+            }
+        }
+
+        let after = at + 1;
+        let name_length = s[after..]
+            .bytes()
+            .take_while(|&b| b.is_ascii_alphanumeric() || b == b'_')
+            .count();
+        if name_length == 0 {
+            return None;
+        }
+
+        let mut end = after + name_length;
+        if s[end..].starts_with('@') {
+            let domain_scanner = EmailScanner {
+                domain_must_have_dot: true,
+                allowed_hosts: Vec::new(),
+                max_length: None,
+                ip_literal: false,
+                quoted_local: false,
+                #[cfg(feature = "tlds")]
+                require_valid_tld: false,
+            };
+            end = end + 1 + domain_scanner.find_end(&s[end + 1..], None)?;
+        }
+
+        if let Some(max_length) = self.max_length {
+            if end - at > max_length {
+                return None;
+            }
+        }
+        Some(Range { start: at, end })
+    }
+}
@@ -0,0 +1,144 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::scanner::Scanner;
+
+/// Scan for phone numbers like "+44 20 7946 0958" or "(555) 012-3456", starting from the trigger
+/// characters "+", "(" or a digit.
+///
+/// This is a heuristic. To avoid finding all kinds of other numbers, a number needs to start with
+/// "+" or have at least 7 digits, and it can't have more than 15 digits (see E.164). Numbers
+/// without "+" or an area code in parentheses also need groups of digits like in phone numbers,
+/// so that IP addresses, dates and version numbers are not found.
+#[derive(Clone, Debug)]
+pub struct PhoneScanner {
+    pub max_length: Option<usize>,
+}
+
+/// The characters that a phone number can start with.
+pub const TRIGGERS: &[u8] = b"+(0123456789";
+
+const MIN_DIGITS: usize = 7;
+const MIN_DIGITS_INTERNATIONAL: usize = 5;
+const MAX_DIGITS: usize = 15;
+
+impl Scanner for PhoneScanner {
+    fn scan(&self, s: &str, start: usize) -> Option<Range<usize>> {
+        if let Some(c) = s[..start].chars().next_back() {
+            if !Self::can_precede(c) {
+                return None;
+            }
+        }
+
+        let (end, digits) = self.find_end(&s[start..])?;
+        let end = start + end;
+        let min_digits = if s[start..].starts_with('+') {
+            MIN_DIGITS_INTERNATIONAL
+        } else {
+            MIN_DIGITS
+        };
+        if digits < min_digits || digits > MAX_DIGITS {
+            return None;
+        }
+        let number = &s[start..end];
+        if !number.starts_with('+') && !number.contains('(') && !Self::groups_valid(number) {
+            return None;
+        }
+        // Don't find the start of something else, like "5550100abc"
+        if let Some(c) = s[end..].chars().next() {
+            if c.is_alphanumeric() || c == '_' {
+                return None;
+            }
+        }
+        Some(Range { start, end })
+    }
+}
+
+impl PhoneScanner {
+    // Returns the end and the number of digits.
+    fn find_end(&self, s: &str) -> Option<(usize, usize)> {
+        let mut round = 0;
+        let mut digits = 0;
+        let mut end = None;
+
+        let mut previous_is_separator = false;
+        let mut chars = s.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            let is_separator = match c {
+                '0'..='9' => {
+                    if self
+                        .max_length
+                        .map(|max_length| i + 1 > max_length)
+                        .unwrap_or(false)
+                    {
+                        return None;
+                    }
+                    digits += 1;
+                    if digits > MAX_DIGITS {
+                        // Part of a longer number, e.g. an order number
+                        return None;
+                    }
+                    end = Some(i + 1);
+                    false
+                }
+                '+' if i == 0 => false,
+                '(' if round == 0 => {
+                    round += 1;
+                    false
+                }
+                ')' if round == 1 => {
+                    round -= 1;
+                    false
+                }
+                ' ' | '-' | '.' if end.is_some() && !previous_is_separator => {
+                    match chars.peek() {
+                        Some(&(_, next)) if next.is_ascii_digit() || next == '(' => {}
+                        _ => break,
+                    }
+                    true
+                }
+                _ => break,
+            };
+            previous_is_separator = is_separator;
+        }
+
+        if round != 0 {
+            return None;
+        }
+        end.map(|end| (end, digits))
+    }
+
+    // Whether the groups of digits of a national number look like a phone number, e.g. not like
+    // `192.168.100.200`, `2021-01-01` or `1.2.3.4.5.6.7`
+    fn groups_valid(number: &str) -> bool {
+        let groups: Vec<usize> = number
+            .split(|c: char| !c.is_ascii_digit())
+            .map(str::len)
+            .collect();
+        if groups.len() == 1 {
+            return true;
+        }
+        // A group with a single digit is more likely a version number or a list of numbers
+        if groups.iter().any(|&len| len < 2) {
+            return false;
+        }
+        // An ISO 8601 date like `2021-01-01`
+        if groups == [4, 2, 2] {
+            return false;
+        }
+        // Dots are also used in IP addresses, so only accept the common formats `555.012.3456`
+        // and `01.23.45.67.89`
+        if number.contains('.') {
+            return groups == [3, 3, 4] || groups == [2, 2, 2, 2, 2];
+        }
+        true
+    }
+
+    fn can_precede(c: char) -> bool {
+        c.is_whitespace()
+            || match c {
+                '(' | '[' | '{' | '<' | '"' | '\'' | ',' | ';' | ':' => true,
+                _ => false,
+            }
+    }
+}
@@ -1,5 +1,21 @@
-use std::ops::Range;
+use core::ops::Range;
 
+/// Scanner for a kind of link, see `LinkFinder::add_scanner`.
 pub trait Scanner {
+    /// Scan for a link around the trigger character at `trigger_index` in `s`.
+    ///
+    /// `s` is the complete input text and `trigger_index` the byte index of the trigger character
+    /// in it. Returns the byte range of the link in `s`, or `None` if there is no link at this
+    /// position. The range doesn't have to include the trigger, but it must not start before the
+    /// end of the previously found link, otherwise it is ignored. Empty ranges are ignored as
+    /// well, and so are ranges that are out of bounds of `s` or not on `char` boundaries.
     fn scan(&self, s: &str, trigger_index: usize) -> Option<Range<usize>>;
+
+    /// Like `scan`, but with the reason why there is no link at this position, see
+    /// `LinkFinder::explain`.
+    ///
+    /// The default implementation calls `scan` and doesn't give a specific reason.
+    fn scan_explained(&self, s: &str, trigger_index: usize) -> Result<Range<usize>, &'static str> {
+        self.scan(s, trigger_index).ok_or("no link found")
+    }
 }
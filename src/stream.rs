@@ -0,0 +1,91 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::finder::{LinkFinder, OwnedLink};
+
+/// Finds links in input text that arrives in chunks, e.g. from a network stream.
+///
+/// Links can be split across chunks, e.g. `http://exa` and `mple.org/`. So the text after the
+/// last whitespace is held back until more input is fed, or until `finish` is called. Start and
+/// end of the returned links are relative to the whole input.
+///
+/// ```
+/// use linkify::LinkFinder;
+///
+/// let finder = LinkFinder::new();
+/// let mut stream = finder.stream();
+/// assert!(stream.feed("See http://exa").is_empty());
+/// let links = stream.feed("mple.org/ and ");
+/// assert_eq!("http://example.org/", links[0].as_str());
+/// assert_eq!(4, links[0].start());
+/// let links = stream.finish();
+/// assert!(links.is_empty());
+/// ```
+#[derive(Debug)]
+pub struct LinkStream<'f> {
+    finder: &'f LinkFinder,
+    buffer: String,
+    // The index of the start of `buffer` in the whole input
+    offset: usize,
+}
+
+impl<'f> LinkStream<'f> {
+    pub(crate) fn new(finder: &'f LinkFinder) -> LinkStream<'f> {
+        LinkStream {
+            finder,
+            buffer: String::new(),
+            offset: 0,
+        }
+    }
+
+    /// Add the next chunk of input text, returns the links that are complete.
+    ///
+    /// Text without whitespace is held back, so if the input doesn't contain whitespace, links
+    /// are only found when calling `finish`.
+    pub fn feed(&mut self, chunk: &str) -> Vec<OwnedLink> {
+        self.buffer.push_str(chunk);
+
+        // Links don't continue after whitespace, except for some kinds that allow single spaces
+        // depending on the next character. So the whitespace must not be the last character.
+        let last = self.buffer.len() - self.buffer.chars().next_back().map_or(0, char::len_utf8);
+        let cut = match self.buffer[..last]
+            .char_indices()
+            .rev()
+            .find(|&(_, c)| c.is_whitespace())
+        {
+            Some((i, _)) => i,
+            None => return Vec::new(),
+        };
+
+        // Scan the whole buffer so that scanners see the text after the cut too
+        let mut links = Vec::new();
+        let mut cut = cut;
+        for link in self.finder.links(&self.buffer) {
+            if link.end() > cut {
+                // The link might continue with the next chunk. Keep it, including the character
+                // before it, which is needed for checking where the link starts.
+                let before = self.buffer[..link.start()]
+                    .chars()
+                    .next_back()
+                    .map_or(0, char::len_utf8);
+                cut = cut.min(link.start() - before);
+                break;
+            }
+            links.push(OwnedLink::from(link).with_offset(self.offset));
+        }
+
+        // Keep the character at the cut, it's needed for checking what's before the next link
+        self.buffer.drain(..cut);
+        self.offset += cut;
+        links
+    }
+
+    /// Finish the input, returns the links in the text that was held back.
+    pub fn finish(self) -> Vec<OwnedLink> {
+        let offset = self.offset;
+        self.finder
+            .links(&self.buffer)
+            .map(|link| OwnedLink::from(link).with_offset(offset))
+            .collect()
+    }
+}
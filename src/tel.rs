@@ -0,0 +1,74 @@
+use core::ops::Range;
+
+use crate::scanner::Scanner;
+
+/// Scan for telephone links starting from the trigger character ":", requires "tel:".
+///
+/// Based on RFC 3966, but only handles the number itself, not parameters like ";ext=".
+#[derive(Clone, Debug)]
+pub struct TelScanner {
+    pub max_length: Option<usize>,
+}
+
+impl Scanner for TelScanner {
+    fn scan(&self, s: &str, colon: usize) -> Option<Range<usize>> {
+        let start = colon.checked_sub("tel".len())?;
+        if !s.as_bytes()[start..colon].eq_ignore_ascii_case(b"tel") {
+            return None;
+        }
+        // Don't find "tel:" at the end of a word like "hotel:"
+        if let Some(c) = s[..start].chars().next_back() {
+            if c.is_alphanumeric() {
+                return None;
+            }
+        }
+
+        let after = colon + 1;
+        let max_end_length = match self.max_length {
+            Some(max_length) => Some(max_length.checked_sub(after - start)?),
+            None => None,
+        };
+        let end = after + self.find_end(&s[after..], max_end_length)?;
+        Some(Range { start, end })
+    }
+}
+
+impl TelScanner {
+    // See "telephone-subscriber" in RFC 3966. Digits can be separated by "-", "." and parentheses
+    // (visual separators), we also allow single spaces between them.
+    fn find_end(&self, s: &str, max_length: Option<usize>) -> Option<usize> {
+        let mut round = 0;
+        let mut end = None;
+
+        let mut chars = s.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '0'..='9' => {
+                    if max_length
+                        .map(|max_length| i + 1 > max_length)
+                        .unwrap_or(false)
+                    {
+                        return None;
+                    }
+                    end = Some(i + 1);
+                }
+                '+' if i == 0 => {}
+                '-' | '.' => {}
+                '(' => round += 1,
+                ')' => {
+                    round -= 1;
+                    if round < 0 {
+                        break;
+                    }
+                }
+                ' ' if end.is_some() => match chars.peek() {
+                    Some(&(_, next)) if next.is_ascii_digit() || next == '(' => {}
+                    _ => break,
+                },
+                _ => break,
+            }
+        }
+
+        end
+    }
+}
@@ -1,11 +1,75 @@
-use std::ops::Range;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
 
+use crate::ip;
 use crate::scanner::Scanner;
+#[cfg(feature = "tlds")]
+use crate::tlds;
+
+/// The schemes that are allowed with `require_secure_schemes`, using TLS or SSH.
+const SECURE_SCHEMES: &[&str] = &["https", "wss", "ftps", "sftp"];
 
 /// Scan for URLs starting from the trigger character ":", requires "://".
 ///
 /// Based on RFC 3986.
-pub struct UrlScanner;
+#[derive(Clone, Debug)]
+pub struct UrlScanner {
+    /// Allowed schemes, all schemes are allowed if empty
+    pub schemes: Vec<String>,
+    /// Schemes that are never allowed, takes precedence over `schemes`
+    pub denied_schemes: Vec<String>,
+    /// Schemes that are found without `//`, like `about` in `about:blank`
+    pub opaque_schemes: Vec<String>,
+    /// Reject URLs with a port that is out of range
+    pub validate_port: bool,
+    /// End URLs at a `%` that is not followed by two hex digits
+    pub validate_percent_encoding: bool,
+    /// Exclude the fragment like `#section` from URLs
+    pub strip_fragment: bool,
+    /// Exclude the query like `?a=b` from URLs, and the fragment after it
+    pub strip_query: bool,
+    /// Only allow schemes in `SECURE_SCHEMES`, in addition to `schemes`
+    pub require_secure_schemes: bool,
+    /// Maximum length of URLs in bytes
+    pub max_length: Option<usize>,
+    /// Maximum length of schemes in bytes
+    pub max_scheme_length: Option<usize>,
+    /// Exclude a slash directly after the authority if it's the last character
+    pub trim_trailing_slash: bool,
+    /// Additional characters that can be part of URLs but not at the end
+    pub stop_chars: Vec<char>,
+    /// Allow underscores in hosts of URLs without a scheme
+    pub underscore_host: bool,
+    /// Reject URLs with a host without a dot, except for `localhost` and IP literals
+    pub host_must_have_dot: bool,
+    /// Stop at closing brackets without a matching opening bracket
+    pub balance_brackets: bool,
+    /// Only find URLs without a scheme after whitespace, an opening bracket or quote
+    pub require_word_boundary: bool,
+    /// Also accept `:/` as the separator between scheme and host, like in `http:/example.org`
+    pub single_slash: bool,
+    /// Find IP addresses followed by a port or path as hosts of URLs without a scheme
+    pub ip_host: bool,
+    /// Find protocol-relative URLs like `//example.org/`, triggered by `/`
+    pub protocol_relative: bool,
+    /// Only allow ASCII characters, see RFC 3986
+    pub strict_ascii: bool,
+    /// End URLs at invisible formatting characters like zero-width spaces and bidi controls
+    pub reject_control_chars: bool,
+    /// Continue URLs after a line break that directly follows a `-`, like in PDFs
+    pub join_soft_wraps: bool,
+    /// Keep a trailing dot of the host if it's followed by `/`, like in `http://example.org./`
+    pub keep_fqdn_dot: bool,
+    /// End URLs preceded by `<` at the next `>`, see "Delimiting a URI in Context" in RFC 3986
+    pub angle_bracket_delimited: bool,
+    /// Allowed top-level domains of URLs without a scheme in lowercase, all are allowed if empty
+    pub allowed_tlds: Vec<String>,
+    /// Reject URLs without a scheme if their top-level domain is not known, unless there are
+    /// `allowed_tlds`
+    #[cfg(feature = "tlds")]
+    pub require_valid_tld: bool,
+}
 
 impl Scanner for UrlScanner {
     /// Scan for an URL at the given separator index in the string.
@@ -15,34 +79,267 @@ impl Scanner for UrlScanner {
     ///
     /// Returns `None` if none was found, or if an invalid separator index was given.
     fn scan(&self, s: &str, separator: usize) -> Option<Range<usize>> {
+        self.scan_explained(s, separator).ok()
+    }
+
+    fn scan_explained(&self, s: &str, separator: usize) -> Result<Range<usize>, &'static str> {
+        if self.ip_host && s[separator..].starts_with('[') {
+            return self.scan_ip_host(s, separator);
+        }
+        if self.protocol_relative && s[separator..].starts_with('/') {
+            return self.scan_protocol_relative(s, separator);
+        }
         // There must be something before separator for scheme or host
         if separator == 0 {
-            return None;
+            return Err("nothing before the separator");
         }
 
         // Detect used separator, being `://` or `.`
         let (is_slash_slash, separator_len) = if s[separator..].starts_with("://") {
             (true, "://".len())
+        } else if self.single_slash && s[separator..].starts_with(":/") {
+            // Broken like `http:/example.org`, treat it like `://`
+            (true, ":/".len())
         } else if s[separator..].starts_with('.') {
+            if self.ip_host {
+                if let Some(start) = Self::find_ipv4_start(&s[..separator]) {
+                    return self.scan_ip_host(s, start);
+                }
+            }
             (false, ".".len())
+        } else if let Some(scheme) = self.opaque_scheme(&s[..separator]) {
+            return self.scan_opaque(s, separator, scheme);
         } else {
-            return None;
+            return Err("not a separator");
         };
         let after_separator = separator + separator_len;
+        if after_separator >= s.len() {
+            return Err("nothing after the separator");
+        }
 
-        if after_separator < s.len() {
-            if let Some(start) = self.find_start(&s[0..separator], is_slash_slash) {
-                if let Some(end) = self.find_end(&s[after_separator..]) {
-                    let range = Range {
-                        start,
-                        end: after_separator + end,
-                    };
-                    return Some(range);
+        let start = if !is_slash_slash && Self::is_www(&s[0..separator]) {
+            separator - "www".len()
+        } else {
+            self.find_start(&s[0..separator], is_slash_slash)?
+        };
+        if separator_len == ":/".len() && separator - start < 2 {
+            // A Windows path like `C:/Users`, not a scheme
+            return Err("drive letter instead of a scheme");
+        }
+        let max_end_length = match self.max_length {
+            Some(max_length) => Some(
+                max_length
+                    .checked_sub(after_separator - start)
+                    .ok_or("longer than the maximum length")?,
+            ),
+            None => None,
+        };
+        let delimited_end = if self.angle_bracket_delimited && s[..start].ends_with('<') {
+            self.find_delimited_end(&s[after_separator..])
+        } else {
+            None
+        };
+        let end = match delimited_end {
+            Some(end) => {
+                if max_end_length.map(|max| end > max).unwrap_or(false) {
+                    return Err("longer than the maximum length");
                 }
+                after_separator + end
+            }
+            None => after_separator + self.find_end(&s[after_separator..], true, max_end_length)?,
+        };
+        let end = if !is_slash_slash && !s[start..separator].contains(':') {
+            self.end_before_non_port(s, after_separator, end)?
+        } else {
+            end
+        };
+        let authority = Self::authority(&s[after_separator..end]);
+
+        // Without a scheme, an `@` before the path means it's an email address
+        if !is_slash_slash && authority.contains('@') {
+            return Err("part of an email address");
+        }
+
+        let scheme = if is_slash_slash {
+            Some(&s[start..separator])
+        } else {
+            s[start..separator].find(':').map(|i| &s[start..start + i])
+        };
+        if let Some(scheme) = scheme {
+            if !self.scheme_allowed(scheme) {
+                return Err("scheme not allowed");
             }
         }
+
+        let host = if is_slash_slash {
+            Self::host(authority)
+        } else {
+            let host_start = scheme
+                .map(|scheme| start + scheme.len() + 1)
+                .unwrap_or(start);
+            Self::host(Self::authority(&s[host_start..end]))
+        };
+
+        if scheme.is_none() && self.require_word_boundary && !Self::word_boundary(&s[..start]) {
+            return Err("no word boundary before the host");
+        }
+
+        if scheme.is_none() && !self.tld_allowed(host) {
+            return Err("top-level domain not allowed");
+        }
+
+        // File URLs usually don't have a host, or a Windows drive letter instead of one
+        let is_file = scheme_is(scheme, "file");
+
+        if self.host_must_have_dot && !is_file && !Self::host_has_dot(host) {
+            return Err("host without a dot");
+        }
+
+        if is_slash_slash && !Self::ip_literal_valid(authority) {
+            return Err("invalid IP literal host");
+        }
+
+        if self.validate_port && !Self::port_valid(authority) {
+            return Err("invalid port");
+        }
+
+        // Only trim the slash of an empty path, not one that is part of a longer path
+        let path_is_slash = after_separator + authority.len() + 1 == end && s[..end].ends_with('/');
+        let end = if self.trim_trailing_slash && path_is_slash {
+            end - 1
+        } else {
+            end
+        };
+
+        Ok(Range { start, end })
+    }
+}
+
+/// Split an URL that was found into its scheme, if it has one, and the rest after `:` or `://`.
+pub fn split_scheme(url: &str) -> (Option<&str>, &str) {
+    let colon = match url.find(':') {
+        Some(colon) => colon,
+        None => return (None, url),
+    };
+    let scheme = &url[..colon];
+    let valid = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.');
+    if !valid {
+        return (None, url);
+    }
+    let rest = &url[colon + 1..];
+    if rest.starts_with("//") {
+        return (Some(scheme), &rest[2..]);
+    }
+    // Without `//`, `example.org:8080` has a port instead of a scheme
+    let port = rest.split('/').next().unwrap_or("");
+    if scheme.contains('.') || (!port.is_empty() && port.bytes().all(|b| b.is_ascii_digit())) {
+        (None, url)
+    } else {
+        (Some(scheme), rest)
+    }
+}
+
+/// The host of an URL that was found, see `Link::host`.
+pub fn host(url: &str) -> Option<&str> {
+    let (scheme, rest) = split_scheme(url);
+    if is_opaque(scheme, url) {
+        return None;
+    }
+    let authority = UrlScanner::authority(without_slashes(scheme, rest));
+    if scheme_is(scheme, "file") && is_drive_letter(authority) {
+        return None;
+    }
+    let host = UrlScanner::host(authority);
+    if host.is_empty() {
         None
+    } else {
+        Some(host)
+    }
+}
+
+/// The index of the `?` that starts the query of an URL that was found, see `Link::without_query`.
+/// URIs like `magnet:?xt=...` don't have `//`, so the query is all there is, not a query.
+pub fn query_start(url: &str) -> Option<usize> {
+    let (scheme, _) = split_scheme(url);
+    if is_opaque(scheme, url) {
+        return None;
     }
+    let hash = url.find('#').unwrap_or(url.len());
+    url[..hash].find('?')
+}
+
+/// The userinfo of an URL that was found, see `Link::userinfo`.
+pub fn userinfo(url: &str) -> Option<&str> {
+    let (scheme, rest) = split_scheme(url);
+    if is_opaque(scheme, url) {
+        return None;
+    }
+    let authority = UrlScanner::authority(without_slashes(scheme, rest));
+    authority.rfind('@').map(|i| &authority[..i])
+}
+
+// The rest of a protocol-relative URL like `//example.org/` after the slashes
+fn without_slashes<'a>(scheme: Option<&str>, rest: &'a str) -> &'a str {
+    if scheme.is_none() && rest.starts_with("//") {
+        &rest[2..]
+    } else {
+        rest
+    }
+}
+
+// Whether an URL has a scheme but no `//`, like `about:blank` or `data:` URIs. They don't have
+// an authority.
+fn is_opaque(scheme: Option<&str>, url: &str) -> bool {
+    match scheme {
+        Some(scheme) => !url[scheme.len() + 1..].starts_with("//"),
+        None => false,
+    }
+}
+
+fn scheme_is(scheme: Option<&str>, name: &str) -> bool {
+    scheme
+        .map(|scheme| scheme.eq_ignore_ascii_case(name))
+        .unwrap_or(false)
+}
+
+// A Windows drive letter like `C:`, or `C|` in old file URLs. See RFC 8089 appendix E.2.
+fn is_drive_letter(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    bytes.len() == 2 && bytes[0].is_ascii_alphabetic() && (bytes[1] == b':' || bytes[1] == b'|')
+}
+
+// Invisible formatting characters (general category "Cf") like zero-width spaces, joiners, and
+// bidi marks and overrides. See "Bidirectional Text Spoofing" in Unicode Technical Report #36.
+fn is_format_char(c: char) -> bool {
+    match c {
+        '\u{00AD}'
+        | '\u{061C}'
+        | '\u{180E}'
+        | '\u{200B}'..='\u{200F}'
+        | '\u{202A}'..='\u{202E}'
+        | '\u{2060}'..='\u{2064}'
+        | '\u{2066}'..='\u{206F}'
+        | '\u{FEFF}'
+        | '\u{FFF9}'..='\u{FFFB}' => true,
+        _ => false,
+    }
+}
+
+// Characters of a host name, including international ones
+fn is_host_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '.' || c == '_'
+}
+
+// Whether `s` starts with a percent-encoded octet like `%2F`
+fn is_percent_encoded(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    bytes.len() >= 3
+        && bytes[0] == b'%'
+        && bytes[1].is_ascii_hexdigit()
+        && bytes[2].is_ascii_hexdigit()
 }
 
 impl UrlScanner {
@@ -50,23 +347,60 @@ impl UrlScanner {
     // true because the URL will have a scheme for sure. If seraching before the `.` separator, it
     // should be `false` as we might search over the scheme definition for the scheme being optional.
     // See "scheme" in RFC 3986
-    fn find_start(&self, s: &str, mut has_scheme: bool) -> Option<usize> {
+    fn find_start(&self, s: &str, mut has_scheme: bool) -> Result<usize, &'static str> {
         let mut first = None;
         let mut special = None;
+        let mut scheme_end = if has_scheme { Some(s.len()) } else { None };
+        // Without a scheme, the first character after `s` is the `.` separator
+        let mut previous = '.';
         for (i, c) in s.char_indices().rev() {
             match c {
                 'a'..='z' | 'A'..='Z' => first = Some(i),
                 '0'..='9' => special = Some(i),
-                '/' if !has_scheme => special = Some(i),
+                '/' if !has_scheme => {
+                    // Not the start of a host, but part of a path or a partial scheme separator
+                    // like in `//example.org` or `://example.org`
+                    return Err("slash before the host");
+                }
+                '@' if !has_scheme => {
+                    // Part of an email address, not a host
+                    return Err("part of an email address");
+                }
                 ':' if !has_scheme => {
                     has_scheme = true;
+                    scheme_end = Some(i);
                     special = Some(i)
                 }
+                '.' if !has_scheme && (previous == '.' || previous == '-' || previous == '+') => {
+                    // Not a host, a label can't be empty or start with punctuation. Stopping here
+                    // also avoids scanning runs like "....." again for each dot.
+                    break;
+                }
                 '+' | '-' | '.' => {}
+                '_' if !has_scheme && self.underscore_host => first = Some(i),
+                _ if self.reject_control_chars && is_format_char(c) => {
+                    if !has_scheme && s[..i].chars().next_back().map_or(false, is_host_char) {
+                        // Inside of the host, ending it here would find a different host
+                        return Err("format character in the host");
+                    }
+                    break;
+                }
+                _ if !has_scheme && !self.strict_ascii && c >= '\u{80}' => {
+                    // International domain name, see RFC 3987
+                    first = Some(i)
+                }
                 _ => {
                     break;
                 }
             }
+            if let (Some(max_scheme_length), Some(scheme_end)) =
+                (self.max_scheme_length, scheme_end)
+            {
+                if scheme_end - i > max_scheme_length {
+                    return Err("scheme longer than the maximum length");
+                }
+            }
+            previous = c;
         }
 
         // We don't want to extract "abc://foo" out of "1abc://foo".
@@ -75,24 +409,465 @@ impl UrlScanner {
             if let Some(special) = special {
                 // Comparing the byte indices with `- 1` is ok as scheme must be ASCII
                 if first > 0 && first - 1 == special {
-                    return None;
+                    return Err("scheme or host preceded by a digit or colon");
                 }
             }
         }
-        first
+        first.ok_or("no valid start")
+    }
+
+    // URIs with an opaque part like `about:blank` or `data:` URIs don't have `://`. They're only
+    // found if their scheme is in `opaque_schemes`, or for `data:` and `magnet:` in `schemes`.
+    fn opaque_scheme<'s>(&'s self, s: &str) -> Option<&'s str> {
+        let explicitly_allowed = |scheme: &&str| {
+            self.schemes
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(scheme))
+        };
+        self.opaque_schemes
+            .iter()
+            .map(|scheme| scheme.as_str())
+            .chain(
+                ["data", "magnet"]
+                    .iter()
+                    .cloned()
+                    .filter(explicitly_allowed),
+            )
+            .find(|&scheme| self.scheme_allowed(scheme) && Self::ends_with_scheme(s, scheme))
+    }
+
+    fn ends_with_scheme(s: &str, scheme: &str) -> bool {
+        let len = scheme.len();
+        if s.len() < len || !s.as_bytes()[s.len() - len..].eq_ignore_ascii_case(scheme.as_bytes()) {
+            return false;
+        }
+        match s[..s.len() - len].chars().next_back() {
+            Some(c) => !(c.is_alphanumeric() || c == '+' || c == '-' || c == '.'),
+            None => true,
+        }
+    }
+
+    // The rest of opaque URIs ends like the path of other URLs, `data:` and `magnet:` URIs need
+    // to be valid as well
+    fn scan_opaque(
+        &self,
+        s: &str,
+        colon: usize,
+        scheme: &str,
+    ) -> Result<Range<usize>, &'static str> {
+        let start = colon - scheme.len();
+        let after = colon + 1;
+        let max_end_length = match self.max_length {
+            Some(max_length) => Some(
+                max_length
+                    .checked_sub(after - start)
+                    .ok_or("longer than the maximum length")?,
+            ),
+            None => None,
+        };
+        let end = after + self.find_end(&s[after..], false, max_end_length)?;
+        if scheme.eq_ignore_ascii_case("data") {
+            Self::data_valid(&s[after..end])?;
+        } else if scheme.eq_ignore_ascii_case("magnet") && !s[after..end].starts_with('?') {
+            return Err("magnet link without a query");
+        }
+        Ok(Range { start, end })
+    }
+
+    // See "dataurl" in RFC 2397, like `data:image/png;base64,iVBORw0KGgo=`
+    fn data_valid(s: &str) -> Result<(), &'static str> {
+        let comma = s.find(',').ok_or("data URI without a comma")?;
+        let media_type_valid = s[..comma]
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$&-^_.+/;=".contains(c));
+        if media_type_valid {
+            Ok(())
+        } else {
+            Err("invalid media type")
+        }
+    }
+
+    // The start of an IPv4 address that ends with `s`, if `s` ends with digits that are not part
+    // of something else
+    fn find_ipv4_start(s: &str) -> Option<usize> {
+        let start = s
+            .rfind(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        if start == s.len() || !s[start..].starts_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+        match s[..start].chars().next_back() {
+            Some(c) if c.is_alphanumeric() || "_-@:/".contains(c) => None,
+            _ => Some(start),
+        }
     }
 
-    fn find_end(&self, s: &str) -> Option<usize> {
+    // An URL without a scheme with an IP address as the host like `192.168.1.1/admin` or
+    // `[::1]:8080`, starting at `start`. It must have a port or path, otherwise it's probably
+    // something else, e.g. a version number.
+    fn scan_ip_host(&self, s: &str, start: usize) -> Result<Range<usize>, &'static str> {
+        if let Some(c) = s[..start].chars().next_back() {
+            if c.is_alphanumeric() || "_-@:/.".contains(c) {
+                return Err("IP address preceded by a host character");
+            }
+        }
+        let end = start + self.find_end(&s[start..], true, self.max_length)?;
+        let authority = Self::authority(&s[start..end]);
+        let host = Self::host(authority);
+        let valid = if host.starts_with('[') {
+            host.ends_with(']') && ip::is_ipv6_literal(&host[1..host.len() - 1])
+        } else {
+            ip::is_ipv4(host)
+        };
+        if !valid {
+            return Err("invalid IP address host");
+        }
+        if host.len() == authority.len() && end == start + host.len() {
+            return Err("IP address host without a port or path");
+        }
+        if authority.contains('@') {
+            return Err("part of an email address");
+        }
+        if self.validate_port && !Self::port_valid(authority) {
+            return Err("invalid port");
+        }
+        Ok(Range { start, end })
+    }
+
+    // A protocol-relative URL like `//example.org/`, see "relative-ref" in RFC 3986. The input at
+    // `start` is the first slash.
+    fn scan_protocol_relative(&self, s: &str, start: usize) -> Result<Range<usize>, &'static str> {
+        if !s[start..].starts_with("//") {
+            return Err("not a separator");
+        }
+        if let Some(c) = s[..start].chars().next_back() {
+            // Not a path like `a//b` or the rest of `http://`
+            if c.is_alphanumeric() || "_-@:/.".contains(c) {
+                return Err("slashes preceded by a host character");
+            }
+        }
+        let after_slashes = start + "//".len();
+        let max_end_length = match self.max_length {
+            Some(max_length) => Some(
+                max_length
+                    .checked_sub("//".len())
+                    .ok_or("longer than the maximum length")?,
+            ),
+            None => None,
+        };
+        let end = after_slashes + self.find_end(&s[after_slashes..], true, max_end_length)?;
+        let authority = Self::authority(&s[after_slashes..end]);
+        let host = Self::host(authority);
+        let plausible = host.starts_with(|c: char| c.is_alphanumeric() || c == '[');
+        if !plausible || !Self::host_has_dot(host) {
+            return Err("no host after the slashes");
+        }
+        if !Self::ip_literal_valid(authority) {
+            return Err("invalid IP literal host");
+        }
+        if !self.tld_allowed(host) {
+            return Err("top-level domain not allowed");
+        }
+        if self.validate_port && !Self::port_valid(authority) {
+            return Err("invalid port");
+        }
+        Ok(Range { start, end })
+    }
+
+    /// The length of an URL that was found without its query or fragment, see `strip_query` and
+    /// `strip_fragment`. Like at the end of URLs, punctuation before the `?` or `#` is not
+    /// included. Returns `None` if there's nothing to strip or nothing would be left.
+    pub fn stripped_len(&self, url: &str) -> Option<usize> {
+        let query = if self.strip_query {
+            query_start(url)
+        } else {
+            None
+        };
+        let cut = match query {
+            Some(query) => query,
+            None if self.strip_fragment => url.find('#')?,
+            None => return None,
+        };
+        self.find_end(&url[..cut], true, None).ok()
+    }
+
+    /// After no URL was found at the `.` separator for `reason` (see `scan_explained`), the index
+    /// before which the other `.` separators can't be the one of an URL either. That's the case
+    /// for the dots in a run of labels like `a.b.c` directly after the separator: Scanning from
+    /// them finds the same start and end, so it fails the same way. Without a letter in between,
+    /// the same is true if there was no valid start, like for `1.2.3`.
+    pub fn failed_until(&self, s: &str, separator: usize, reason: &str) -> usize {
+        if self.ip_host
+            || separator == 0
+            || s.as_bytes()[separator] != b'.'
+            || Self::is_www(&s[..separator])
+        {
+            // Not the same start for the other separators, or the separator is not `.`
+            return separator;
+        }
+        let letters = reason != "no valid start";
+        let bytes = s.as_bytes();
+        let mut until = separator;
+        let mut dot = separator;
+        loop {
+            let label_end = bytes[dot + 1..]
+                .iter()
+                .position(|&b| !(b.is_ascii_alphanumeric() || b == b'-'))
+                .map_or(bytes.len(), |i| dot + 1 + i);
+            let label = &bytes[dot + 1..label_end];
+            // A label must start with a letter or digit, otherwise `find_start` stops there
+            if !label.first().map_or(false, u8::is_ascii_alphanumeric)
+                || (!letters && label.iter().any(u8::is_ascii_alphabetic))
+            {
+                return until;
+            }
+            until = label_end;
+            if bytes.get(label_end) != Some(&b'.') {
+                return until;
+            }
+            dot = label_end;
+        }
+    }
+
+    // Without a scheme, only digits can follow a colon in the authority like in `example.org:8080`.
+    // Otherwise the URL ends before the colon, e.g. `example.org:foo` is not a host with a port.
+    fn end_before_non_port(
+        &self,
+        s: &str,
+        after_separator: usize,
+        end: usize,
+    ) -> Result<usize, &'static str> {
+        let authority = Self::authority(&s[after_separator..end]);
+        if authority.contains('@') {
+            // Checked later, it's part of an email address
+            return Ok(end);
+        }
+        match authority.find(':') {
+            Some(colon) if !authority[colon + 1..].bytes().all(|b| b.is_ascii_digit()) => {
+                let before_colon = &s[after_separator..after_separator + colon];
+                Ok(after_separator + self.find_end(before_colon, true, None)?)
+            }
+            _ => Ok(end),
+        }
+    }
+
+    // Whether an URL without a scheme can start after `s`
+    fn word_boundary(s: &str) -> bool {
+        match s.chars().next_back() {
+            Some(c) => c.is_whitespace() || "([{<\"'`".contains(c),
+            None => true,
+        }
+    }
+
+    // A `www` label is a strong signal for the start of a host without scheme, e.g. in
+    // `see:www.example.org` we don't want to include `see:`. After a `/` or `@` it's part of a
+    // path or an email address instead, `find_start` rejects those.
+    fn is_www(s: &str) -> bool {
+        if s.len() < "www".len() || !s.as_bytes()[s.len() - 3..].eq_ignore_ascii_case(b"www") {
+            return false;
+        }
+        match s[..s.len() - 3].chars().next_back() {
+            Some(c) => {
+                !(c.is_alphanumeric() || c == '-' || c == '.' || c == '_' || c == '/' || c == '@')
+            }
+            None => true,
+        }
+    }
+
+    fn scheme_allowed(&self, scheme: &str) -> bool {
+        let matches = |candidate: &String| candidate.eq_ignore_ascii_case(scheme);
+        if self.denied_schemes.iter().any(matches) {
+            return false;
+        }
+        if self.require_secure_schemes
+            && !SECURE_SCHEMES
+                .iter()
+                .any(|secure| secure.eq_ignore_ascii_case(scheme))
+        {
+            return false;
+        }
+        self.schemes.is_empty() || self.schemes.iter().any(matches)
+    }
+
+    // The part of `s` before the path, query or fragment
+    fn authority(s: &str) -> &str {
+        let mut in_ip_literal = false;
+        for (i, c) in s.char_indices() {
+            match c {
+                '[' => in_ip_literal = true,
+                ']' => in_ip_literal = false,
+                '/' | '?' | '#' if !in_ip_literal => return &s[..i],
+                _ => {}
+            }
+        }
+        s
+    }
+
+    // The brackets of an IP literal host are only balanced by `find_end`, check that there's
+    // an actual IPv6 address inside of them. See "IP-literal" in RFC 3986.
+    fn ip_literal_valid(authority: &str) -> bool {
+        let host = match authority.rfind('@') {
+            Some(i) => &authority[i + 1..],
+            None => authority,
+        };
+        if !host.starts_with('[') {
+            return true;
+        }
+        match host.find(']') {
+            Some(i) => ip::is_ipv6_literal(&host[1..i]),
+            None => false,
+        }
+    }
+
+    // The host of an authority, without userinfo and port. See "host" in RFC 3986.
+    fn host(authority: &str) -> &str {
+        let host_port = match authority.rfind('@') {
+            Some(i) => &authority[i + 1..],
+            None => authority,
+        };
+        if host_port.starts_with('[') {
+            return match host_port.find(']') {
+                Some(i) => &host_port[..=i],
+                None => host_port,
+            };
+        }
+        match host_port.rfind(':') {
+            Some(i) => &host_port[..i],
+            None => host_port,
+        }
+    }
+
+    // IP literals and `localhost` are allowed without a dot, a trailing dot doesn't count.
+    fn host_has_dot(host: &str) -> bool {
+        host.starts_with('[')
+            || host.eq_ignore_ascii_case("localhost")
+            || host.trim_end_matches('.').contains('.')
+    }
+
+    // Check the last label of the host against the allowed or known top-level domains
+    fn tld_allowed(&self, host: &str) -> bool {
+        #[cfg(feature = "tlds")]
+        let require_valid_tld = self.require_valid_tld;
+        #[cfg(not(feature = "tlds"))]
+        let require_valid_tld = false;
+        if self.allowed_tlds.is_empty() && !require_valid_tld {
+            return true;
+        }
+
+        let host = host.trim_end_matches('.');
+        let tld = match host.rfind('.') {
+            Some(i) => &host[i + 1..],
+            None => host,
+        };
+        let tld = tld.to_lowercase();
+        if !self.allowed_tlds.is_empty() {
+            return self.allowed_tlds.contains(&tld);
+        }
+
+        #[cfg(feature = "tlds")]
+        {
+            tlds::is_known(&tld)
+        }
+        #[cfg(not(feature = "tlds"))]
+        {
+            true
+        }
+    }
+
+    // A port must be in the range of `u16`, but 0 is reserved. Anything after the colon that is not
+    // only digits is not a port, see "port" in RFC 3986.
+    fn port_valid(authority: &str) -> bool {
+        let host_port = match authority.rfind('@') {
+            Some(i) => &authority[i + 1..],
+            None => authority,
+        };
+        let port = match host_port.rfind(':') {
+            Some(i) => &host_port[i + 1..],
+            None => return true,
+        };
+        if port.is_empty() || !port.bytes().all(|b| b.is_ascii_digit()) {
+            return true;
+        }
+        port.parse::<u16>().map(|port| port > 0).unwrap_or(false)
+    }
+
+    // For an URL delimited like `<http://example.org/>`, the end is at the closing `>`, regardless
+    // of punctuation or brackets before it. Returns `None` if there's no `>` before whitespace,
+    // then the URL is not delimited and the normal rules apply.
+    fn find_delimited_end(&self, s: &str) -> Option<usize> {
+        let mut in_authority = true;
+        for (i, c) in s.char_indices() {
+            match c {
+                '>' if i > 0 => return Some(i),
+                '>' | '<' => return None,
+                _ if c.is_whitespace() || c.is_control() => return None,
+                _ if self.reject_control_chars && is_format_char(c) => return None,
+                _ if self.strict_ascii && !c.is_ascii() => return None,
+                '%' if self.validate_percent_encoding && !is_percent_encoded(&s[i..]) => {
+                    return None
+                }
+                '\'' if in_authority && !Self::userinfo_follows(&s[i..]) => return None,
+                '/' | '?' | '#' => in_authority = false,
+                _ => {}
+            }
+        }
+        None
+    }
+
+    // Whether `s` is inside of the userinfo, which ends with an `@` before the host
+    fn userinfo_follows(s: &str) -> bool {
+        for c in s.chars() {
+            match c {
+                '@' => return true,
+                '/' | '?' | '#' | '<' | '>' | '"' => return false,
+                _ if c.is_whitespace() || c.is_control() => return false,
+                _ => {}
+            }
+        }
+        false
+    }
+
+    // Returns an error if the URL would be longer than `max_length`, to not scan further than
+    // needed. `starts_in_authority` is whether `s` starts with (a part of) the authority, which
+    // ends at the first `/`, `?` or `#`.
+    fn find_end(
+        &self,
+        s: &str,
+        starts_in_authority: bool,
+        max_length: Option<usize>,
+    ) -> Result<usize, &'static str> {
         let mut round = 0;
         let mut square = 0;
         let mut curly = 0;
         let mut single_quote = false;
 
         let mut previous_can_be_last = true;
+        let mut previous = None;
+        let mut in_authority = starts_in_authority;
         let mut end = None;
 
-        for (i, c) in s.char_indices() {
+        let bytes = s.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            // Most URLs are ASCII, so only decode UTF-8 for non-ASCII characters
+            let c = if bytes[i] < 0x80 {
+                char::from(bytes[i])
+            } else {
+                s[i..].chars().next().unwrap()
+            };
             let can_be_last = match c {
+                '\r' | '\n' if self.join_soft_wraps && previous == Some('-') => {
+                    // A long URL that was hyphenated when wrapping it, like `example.org/very-`
+                    // followed by `long/path` on the next line
+                    match Self::soft_wrap_len(&s[i..]) {
+                        Some(len) => {
+                            i += len;
+                            continue;
+                        }
+                        None => break,
+                    }
+                }
                 '\u{00}'..='\u{1F}' | ' ' | '\"' | '<' | '>' | '`' | '\u{7F}'..='\u{9F}' => {
                     // These can never be part of an URL, so stop now. See RFC 3986 and RFC 3987.
                     // Some characters are not in the above list, even they are not in "unreserved"
@@ -102,13 +877,54 @@ impl UrlScanner {
                     // below, we require the braces to be balanced.
                     break;
                 }
+                _ if self.strict_ascii && !c.is_ascii() => {
+                    // Only RFC 3986 URIs, not international ones
+                    break;
+                }
+                _ if self.reject_control_chars && is_format_char(c) => {
+                    // Invisible characters that can be used to make an URL look like another one.
+                    // Inside of the host, ending the URL here would find a different host.
+                    if in_authority && Self::host_continues(&s[i..]) {
+                        return Err("format character in the host");
+                    }
+                    break;
+                }
+                '%' if self.validate_percent_encoding && !is_percent_encoded(&s[i..]) => {
+                    // Not a valid escape like `%20`, see "Percent-Encoding" in RFC 3986
+                    break;
+                }
                 '?' | '!' | '.' | ',' | ':' | ';' => {
                     // These may be part of an URL but not at the end
                     false
                 }
+                '\u{3001}'
+                | '\u{3002}'
+                | '\u{3008}'..='\u{3011}'
+                | '\u{3014}'..='\u{301B}'
+                | '\u{FF01}'
+                | '\u{FF08}'
+                | '\u{FF09}'
+                | '\u{FF0C}'
+                | '\u{FF0E}'
+                | '\u{FF1A}'
+                | '\u{FF1B}'
+                | '\u{FF1F}'
+                | '\u{FF61}'..='\u{FF64}' => {
+                    // CJK and fullwidth punctuation like "。" and "，", and brackets like "「" and
+                    // "（". They are used directly after URLs in CJK text without a space, so
+                    // stop now.
+                    break;
+                }
+                '(' | ')' | '[' | ']' | '{' | '}' if !self.balance_brackets => true,
+                '/' if self.keep_fqdn_dot && in_authority && previous == Some('.') => {
+                    // The dot at the end of a fully qualified domain name like `example.org.`
+                    in_authority = false;
+                    true
+                }
                 '/' => {
                     // This may be part of an URL and at the end, but not if the previous character
                     // can't be the end of an URL
+                    in_authority = false;
                     previous_can_be_last
                 }
                 '(' => {
@@ -149,19 +965,52 @@ impl UrlScanner {
                     }
                     true
                 }
+                '\'' if in_authority && !Self::userinfo_follows(&s[i..]) => {
+                    // Not part of a host, e.g. in `'example.org'`. It can be part of the
+                    // userinfo though, like in `http://o'brien@example.org/`.
+                    break;
+                }
                 '\'' => {
                     single_quote = !single_quote;
                     // A single quote can only be the end of an URL if there's an even number
                     !single_quote
                 }
-                _ => true,
+                _ => !self.stop_chars.contains(&c),
             };
             if can_be_last {
-                end = Some(i + c.len_utf8());
+                let new_end = i + c.len_utf8();
+                if max_length
+                    .map(|max_length| new_end > max_length)
+                    .unwrap_or(false)
+                {
+                    return Err("longer than the maximum length");
+                }
+                end = Some(new_end);
             }
             previous_can_be_last = can_be_last;
+            previous = Some(c);
+            if c == '?' || c == '#' {
+                in_authority = false;
+            }
+            i += c.len_utf8();
         }
 
-        end
+        end.ok_or("no valid end")
+    }
+
+    // Whether the host continues after the format characters at the start of `s`
+    fn host_continues(s: &str) -> bool {
+        s.chars()
+            .find(|&c| !is_format_char(c))
+            .map_or(false, is_host_char)
+    }
+
+    // The length of the line break at the start of `s` if the URL continues after it
+    fn soft_wrap_len(s: &str) -> Option<usize> {
+        let len = if s.starts_with("\r\n") { 2 } else { 1 };
+        match s[len..].chars().next() {
+            Some(c) if !c.is_whitespace() && !c.is_control() => Some(len),
+            _ => None,
+        }
     }
 }
@@ -0,0 +1,62 @@
+use linkify::LinkFinder;
+use linkify::LinkKind;
+
+#[test]
+fn valid_utf8() {
+    let finder = LinkFinder::new();
+    let links: Vec<_> = finder
+        .links_bytes("ä http://example.org/ö foo@example.com".as_bytes())
+        .collect();
+
+    assert_eq!(links.len(), 2);
+    assert_eq!(links[0].as_str(), "http://example.org/ö");
+    assert_eq!(links[0].start(), 3);
+    assert_eq!(links[0].end(), 24);
+    assert_eq!(links[0].kind(), &LinkKind::Url);
+    assert_eq!(links[1].as_str(), "foo@example.com");
+    assert_eq!(links[1].start(), 25);
+    assert_eq!(links[1].end(), 40);
+    assert_eq!(links[1].kind(), &LinkKind::Email);
+}
+
+#[test]
+fn invalid_utf8() {
+    assert_linked(b"\xffhttp://example.org/\xfe", &["http://example.org/"]);
+    assert_linked(b"http://example.org/a\xffb", &["http://example.org/a"]);
+    assert_linked(
+        b"\xc3http://example.org/ \xe2\x82 foo@example.com\xf0\x9f",
+        &["http://example.org/", "foo@example.com"],
+    );
+    // Invalid bytes separate the scheme from the rest
+    assert_linked(b"http\xff://example.org/", &[]);
+}
+
+#[test]
+fn offsets() {
+    let finder = LinkFinder::new();
+    let input = b"\xff\xfe http://example.org/ \xff foo@example.com \xe2\x82";
+    for link in finder.links_bytes(input) {
+        assert_eq!(&input[link.start()..link.end()], link.as_str().as_bytes());
+    }
+}
+
+#[test]
+fn empty() {
+    assert_linked(b"", &[]);
+    assert_linked(b"\xff\xff", &[]);
+}
+
+#[test]
+fn max_links() {
+    let mut finder = LinkFinder::new();
+    finder.max_links(Some(2));
+    let input = b"http://a.com \xff http://b.com \xff http://c.com";
+    let links: Vec<_> = finder.links_bytes(input).map(|l| l.as_str()).collect();
+    assert_eq!(links, &["http://a.com", "http://b.com"]);
+}
+
+fn assert_linked(input: &[u8], expected: &[&str]) {
+    let finder = LinkFinder::new();
+    let links: Vec<_> = finder.links_bytes(input).map(|l| l.as_str()).collect();
+    assert_eq!(links, expected, "links in {:?}", input);
+}
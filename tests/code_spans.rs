@@ -0,0 +1,90 @@
+mod common;
+
+use crate::common::assert_linked_with;
+use linkify::LinkFinder;
+
+#[test]
+fn default_finds_links_in_code() {
+    let finder = LinkFinder::new();
+    assert_linked_with(&finder, "`http://example.org`", "`|http://example.org|`");
+}
+
+#[test]
+fn skip_code_spans() {
+    let mut finder = LinkFinder::new();
+    finder.skip_code_spans(true);
+
+    assert_linked_with(&finder, "`http://example.org`", "`http://example.org`");
+    assert_linked_with(
+        &finder,
+        "See `curl http://example.org` or http://example.com",
+        "See `curl http://example.org` or |http://example.com|",
+    );
+    assert_linked_with(
+        &finder,
+        "`a` http://example.org `b`",
+        "`a` |http://example.org| `b`",
+    );
+    assert_linked_with(&finder, "`foo@example.org`", "`foo@example.org`");
+}
+
+#[test]
+fn multiple_backticks() {
+    let mut finder = LinkFinder::new();
+    finder.skip_code_spans(true);
+
+    assert_linked_with(
+        &finder,
+        "`` a ` http://example.org ``",
+        "`` a ` http://example.org ``",
+    );
+    // A single backtick doesn't close a double backtick span
+    assert_linked_with(
+        &finder,
+        "``a` http://example.org",
+        "``a` |http://example.org|",
+    );
+    assert_linked_with(
+        &finder,
+        "```\nhttp://example.org\n```\nhttp://example.com",
+        "```\nhttp://example.org\n```\n|http://example.com|",
+    );
+}
+
+#[test]
+fn unclosed_backticks() {
+    let mut finder = LinkFinder::new();
+    finder.skip_code_spans(true);
+
+    assert_linked_with(&finder, "` http://example.org", "` |http://example.org|");
+    assert_linked_with(
+        &finder,
+        "``` `http://example.org` ``",
+        "``` `http://example.org` ``",
+    );
+    assert_linked_with(
+        &finder,
+        "`` http://example.org ` http://example.com",
+        "`` |http://example.org| ` |http://example.com|",
+    );
+}
+
+#[test]
+fn explain() {
+    let mut finder = LinkFinder::new();
+    finder.skip_code_spans(true);
+    let candidates = finder.explain("`http://example.org`");
+
+    assert_eq!(1, candidates.len());
+    assert_eq!(Some("in a code span"), candidates[0].reason());
+}
+
+#[test]
+fn bytes() {
+    let mut finder = LinkFinder::new();
+    finder.skip_code_spans(true);
+    let input = b"`http://example.org` \xff http://example.com `http://example.net`";
+    let links: Vec<_> = finder.links_bytes(input).map(|l| l.as_str()).collect();
+
+    assert_eq!(vec!["http://example.com"], links);
+}
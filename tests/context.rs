@@ -0,0 +1,55 @@
+use linkify::LinkFinder;
+
+#[test]
+fn context() {
+    assert_context(
+        "See http://example.org.",
+        3,
+        &[("ee ", "http://example.org", ".")],
+    );
+    assert_context(
+        "a@example.com and b@example.com",
+        5,
+        &[
+            ("", "a@example.com", " and "),
+            (" and ", "b@example.com", ""),
+        ],
+    );
+}
+
+#[test]
+fn zero_radius() {
+    assert_context(
+        "See http://example.org.",
+        0,
+        &[("", "http://example.org", "")],
+    );
+}
+
+#[test]
+fn clamped_to_input() {
+    assert_context("http://example.org", 100, &[("", "http://example.org", "")]);
+}
+
+#[test]
+fn multibyte() {
+    assert_context(
+        "äöü http://example.org 😀😁",
+        3,
+        &[("öü ", "http://example.org", " 😀😁")],
+    );
+    assert_context(
+        "äöü http://example.org 😀😁",
+        1,
+        &[(" ", "http://example.org", " ")],
+    );
+}
+
+fn assert_context(input: &str, radius: usize, expected: &[(&str, &str, &str)]) {
+    let finder = LinkFinder::new();
+    let result: Vec<_> = finder
+        .links_with_context(input, radius)
+        .map(|(link, before, after)| (before, link.as_str(), after))
+        .collect();
+    assert_eq!(result, expected);
+}
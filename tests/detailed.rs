@@ -0,0 +1,35 @@
+use linkify::LinkFinder;
+
+#[test]
+fn terminators() {
+    assert_terminators(
+        "See http://example.org.",
+        &[("http://example.org", Some('.'))],
+    );
+    assert_terminators(
+        "(http://example.org/a)",
+        &[("http://example.org/a", Some(')'))],
+    );
+    assert_terminators("http://example.org", &[("http://example.org", None)]);
+    assert_terminators(
+        "<http://example.org/ä>",
+        &[("http://example.org/ä", Some('>'))],
+    );
+    assert_terminators(
+        "a@example.com, http://example.org\n",
+        &[
+            ("a@example.com", Some(',')),
+            ("http://example.org", Some('\n')),
+        ],
+    );
+    assert_terminators("No links", &[]);
+}
+
+fn assert_terminators(input: &str, expected: &[(&str, Option<char>)]) {
+    let finder = LinkFinder::new();
+    let result: Vec<_> = finder
+        .links_detailed(input)
+        .map(|(link, terminator)| (link.as_str(), terminator))
+        .collect();
+    assert_eq!(expected, result.as_slice(), "for input {:?}", input);
+}
@@ -29,6 +29,21 @@ fn allowed_text() {
     );
 }
 
+#[test]
+fn allowed_text_with_other_kinds() {
+    // All of "atext" in RFC 5322 is allowed in the local part, also when other kinds of links
+    // (with some of them as trigger characters) are found
+    let finder = LinkFinder::new();
+    assert_linked_with(&finder, "a+b/c=d@example.org", "|a+b/c=d@example.org|");
+    assert_linked_with(&finder, "a#b@example.org", "|a#b@example.org|");
+    assert_linked_with(&finder, "+1234567@example.org", "|+1234567@example.org|");
+    assert_linked_with(
+        &finder,
+        "!#$%&'*+-/=?^_`{}|~@example.org",
+        "|!#$%&'*+-/=?^_`{}|~@example.org|",
+    );
+}
+
 #[test]
 fn space_separation() {
     assert_linked("foo a@b.com", "foo |a@b.com|");
@@ -47,6 +62,40 @@ fn special_separation() {
     assert_linked(";a@example.com;", ";|a@example.com|;");
 }
 
+#[test]
+fn start_and_end_of_input() {
+    assert_linked("a@example.com", "|a@example.com|");
+    assert_linked("a@example.com.", "|a@example.com|.");
+    assert_linked(".a@example.com", ".|a@example.com|");
+}
+
+#[test]
+fn trailing_punctuation() {
+    // Like for URLs, punctuation after the domain is not included
+    assert_linked("a@example.com.", "|a@example.com|.");
+    assert_linked("a@example.com,", "|a@example.com|,");
+    assert_linked("a@example.com;", "|a@example.com|;");
+    assert_linked("a@example.com:", "|a@example.com|:");
+    assert_linked("a@example.com!", "|a@example.com|!");
+    assert_linked("a@example.com?", "|a@example.com|?");
+    assert_linked("a@example.com...", "|a@example.com|...");
+    assert_linked("a@example.com-", "|a@example.com|-");
+    assert_linked(
+        "Mail a@example.com, b@example.com.",
+        "Mail |a@example.com|, |b@example.com|.",
+    );
+    assert_linked("[a@example.com]", "[|a@example.com|]");
+    assert_linked("<a@example.com>", "<|a@example.com|>");
+}
+
+#[test]
+fn leading_punctuation() {
+    // Unlike for URLs, some punctuation is allowed at the start of the local part (see "atext"
+    // in RFC 5322), so it's included
+    assert_linked("'a@example.com'", "|'a@example.com|'");
+    assert_linked("{a@example.com}", "|{a@example.com|}");
+}
+
 #[test]
 fn dots() {
     assert_not_linked(".@example.com");
@@ -84,6 +133,205 @@ fn domain_must_have_dot_false() {
     assert_linked_with(&finder, "a@b-.", "|a@b|-.");
 }
 
+#[test]
+fn allowed_hosts() {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Email]);
+    finder.email_allowed_hosts(&["monitoring", "localhost"]);
+
+    assert_linked_with(&finder, "alerts@monitoring", "|alerts@monitoring|");
+    assert_linked_with(&finder, "alerts@Monitoring.", "|alerts@Monitoring|.");
+    assert_linked_with(&finder, "root@localhost", "|root@localhost|");
+    assert_linked_with(&finder, "a@b", "a@b");
+    assert_linked_with(&finder, "alerts@monitoring2", "alerts@monitoring2");
+    assert_linked_with(&finder, "foo@example.org", "|foo@example.org|");
+}
+
+#[test]
+fn max_length() {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Email]);
+    finder.max_length(Some(15));
+
+    assert_linked_with(&finder, "foo@example.com", "|foo@example.com|");
+    assert_linked_with(&finder, "foo@example.com.", "|foo@example.com|.");
+    assert_linked_with(&finder, "fooo@example.com", "fooo@example.com");
+    assert_linked_with(&finder, "foo@examplee.com", "foo@examplee.com");
+    assert_linked_with(&finder, "abcdefghijklmnop@a.b", "abcdefghijklmnop@a.b");
+}
+
+#[test]
+fn ip_literal() {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Email]);
+    finder.email_allow_ip_literal(true);
+
+    assert_linked_with(&finder, "postmaster@[10.0.0.1]", "|postmaster@[10.0.0.1]|");
+    assert_linked_with(&finder, "(a@[10.0.0.1]).", "(|a@[10.0.0.1]|).");
+    assert_linked_with(&finder, "a@[IPv6:2001:db8::1]", "|a@[IPv6:2001:db8::1]|");
+    assert_linked_with(&finder, "a@[ipv6:::1]", "|a@[ipv6:::1]|");
+    assert_linked_with(&finder, "a@[10.0.0.256]", "a@[10.0.0.256]");
+    assert_linked_with(&finder, "a@[2001:db8::1]", "a@[2001:db8::1]");
+    assert_linked_with(&finder, "a@[IPv6:zz]", "a@[IPv6:zz]");
+    assert_linked_with(&finder, "a@[10.0.0.1", "a@[10.0.0.1");
+    assert_linked_with(&finder, "a@example.com", "|a@example.com|");
+
+    finder.max_length(Some(12));
+    assert_linked_with(&finder, "a@[10.0.0.1]", "|a@[10.0.0.1]|");
+    assert_linked_with(&finder, "a@[10.0.0.10]", "a@[10.0.0.10]");
+}
+
+#[test]
+fn ip_literal_default() {
+    assert_not_linked("postmaster@[10.0.0.1]");
+}
+
+#[test]
+#[cfg(feature = "tlds")]
+fn require_valid_tld() {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Email]);
+    finder.email_require_valid_tld(true);
+
+    assert_linked_with(&finder, "foo@example.org", "|foo@example.org|");
+    assert_linked_with(&finder, "foo@example.ORG.", "|foo@example.ORG|.");
+    assert_linked_with(&finder, "用户@例子.中国", "|用户@例子.中国|");
+    assert_linked_with(&finder, "a@example.xn--p1ai", "|a@example.xn--p1ai|");
+    assert_linked_with(&finder, "image@2x.png", "image@2x.png");
+    assert_linked_with(&finder, "config@v2.yml", "config@v2.yml");
+
+    finder.email_domain_must_have_dot(false);
+    assert_linked_with(&finder, "a@localhost", "a@localhost");
+
+    finder.email_allowed_hosts(&["localhost"]);
+    assert_linked_with(&finder, "a@localhost", "|a@localhost|");
+
+    finder.email_allow_ip_literal(true);
+    assert_linked_with(&finder, "a@[10.0.0.1]", "|a@[10.0.0.1]|");
+}
+
+#[test]
+fn quoted_local() {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Email]);
+    finder.email_allow_quoted_local(true);
+
+    assert_linked_with(
+        &finder,
+        "\"john doe\"@example.org",
+        "|\"john doe\"@example.org|",
+    );
+    assert_linked_with(
+        &finder,
+        "Mail \"a@b\"@example.org.",
+        "Mail |\"a@b\"@example.org|.",
+    );
+    assert_linked_with(
+        &finder,
+        "\"a \\\" b\"@example.org",
+        "|\"a \\\" b\"@example.org|",
+    );
+    assert_linked_with(&finder, "\"a\\\\\"@example.org", "|\"a\\\\\"@example.org|");
+    assert_linked_with(&finder, "a\"b\"@example.org", "a\"b\"@example.org");
+    assert_linked_with(&finder, "\"\"@example.org", "\"\"@example.org");
+    assert_linked_with(&finder, "b\"@example.org", "b\"@example.org");
+    assert_linked_with(&finder, "\"a\\\"@example.org", "\"a\\\"@example.org");
+    assert_linked_with(&finder, "\"a\nb\"@example.org", "\"a\nb\"@example.org");
+    assert_linked_with(&finder, "\"a\"@b", "\"a\"@b");
+}
+
+#[test]
+fn quoted_local_default() {
+    assert_not_linked("\"john doe\"@example.org");
+}
+
+#[test]
+fn mailto() {
+    assert_linked("mailto:foo@example.org", "|mailto:foo@example.org|");
+    assert_linked("(MAILTO:foo@example.org)", "(|MAILTO:foo@example.org|)");
+    assert_linked(
+        "mailto:foo@example.org?subject=Hi%20there&body=Hello.",
+        "|mailto:foo@example.org?subject=Hi%20there&body=Hello|.",
+    );
+    assert_linked(
+        "<mailto:foo@example.org?subject=Hi>",
+        "<|mailto:foo@example.org?subject=Hi|>",
+    );
+    assert_linked("mailto:foo@example.org?", "|mailto:foo@example.org|?");
+    assert_linked("foo@example.org?subject=Hi", "|foo@example.org|?subject=Hi");
+    assert_linked("xmailto:foo@example.org", "xmailto:|foo@example.org|");
+}
+
+#[test]
+fn mailto_email_address() {
+    let finder = LinkFinder::new();
+    let address = |input| finder.links(input).next().unwrap().email_address();
+
+    assert_eq!(address("mailto:foo@example.org"), Some("foo@example.org"));
+    assert_eq!(
+        address("mailto:foo@example.org?subject=Hi"),
+        Some("foo@example.org")
+    );
+    assert_eq!(address("foo@example.org"), Some("foo@example.org"));
+    assert_eq!(address("http://example.org"), None);
+}
+
+#[test]
+fn email_address_without_tag() {
+    let mut finder = LinkFinder::new();
+    finder.email_allow_quoted_local(true);
+    let address = |input| {
+        finder
+            .links(input)
+            .next()
+            .unwrap()
+            .email_address_without_tag()
+    };
+
+    assert_eq!(
+        address("user+newsletter@example.org"),
+        Some("user@example.org".to_string())
+    );
+    assert_eq!(
+        address("mailto:user+a+b@example.org?subject=Hi"),
+        Some("user@example.org".to_string())
+    );
+    assert_eq!(
+        address("user@example.org"),
+        Some("user@example.org".to_string())
+    );
+    assert_eq!(
+        address("+tag@example.org"),
+        Some("+tag@example.org".to_string())
+    );
+    assert_eq!(
+        address("\"a+b\"@example.org"),
+        Some("\"a+b\"@example.org".to_string())
+    );
+    assert_eq!(address("http://example.org"), None);
+
+    let link = finder.links("user+newsletter@example.org").next().unwrap();
+    assert_eq!(link.as_str(), "user+newsletter@example.org");
+}
+
+#[test]
+fn mailto_max_length() {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Email]);
+    finder.max_length(Some(24));
+
+    assert_linked_with(
+        &finder,
+        "mailto:a@example.org?a=b",
+        "|mailto:a@example.org?a=b|",
+    );
+    assert_linked_with(
+        &finder,
+        "mailto:a@example.org?a=bc",
+        "mailto:a@example.org?a=bc",
+    );
+}
+
 #[test]
 fn multiple() {
     assert_linked(
@@ -102,6 +350,28 @@ fn international() {
     assert_linked("üñîçøðé@üñîçøðé.com", "|üñîçøðé@üñîçøðé.com|");
 }
 
+#[test]
+fn international_domain() {
+    assert_linked("juan@exámple.org", "|juan@exámple.org|");
+    assert_linked("用户@例子.广告", "|用户@例子.广告|");
+    assert_linked("(juan@exámple.org)", "(|juan@exámple.org|)");
+    assert_linked("juan@exámple.org.", "|juan@exámple.org|.");
+    assert_not_linked("juan@exámple");
+
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Email]);
+    finder.email_domain_must_have_dot(false);
+    assert_linked_with(&finder, "juan@exámple", "|juan@exámple|");
+}
+
+#[test]
+fn international_whitespace() {
+    assert_linked("juan@exámple.org\u{a0}foo", "|juan@exámple.org|\u{a0}foo");
+    assert_linked("用户@例子.广告\u{3000}foo", "|用户@例子.广告|\u{3000}foo");
+    assert_linked("foo\u{3000}用户@例子.广告", "foo\u{3000}|用户@例子.广告|");
+    assert_linked("a@example.org\u{85}foo", "|a@example.org|\u{85}foo");
+}
+
 #[test]
 fn trigger_overlap() {
     let finder = LinkFinder::new();
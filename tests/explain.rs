@@ -0,0 +1,89 @@
+use linkify::LinkFinder;
+use linkify::LinkKind;
+
+#[test]
+fn accepted() {
+    let finder = LinkFinder::new();
+    let candidates = finder.explain("See http://example.org");
+
+    assert_eq!(1, candidates.len());
+    let candidate = &candidates[0];
+    assert_eq!(8, candidate.trigger_index());
+    assert_eq!(&LinkKind::Url, candidate.kind());
+    assert_eq!(Some("http"), candidate.scheme());
+    assert_eq!(None, candidate.reason());
+    assert_eq!("http://example.org", candidate.link().unwrap().as_str());
+}
+
+#[test]
+fn only_triggers_of_kinds() {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Email]);
+    let candidates = finder.explain("http://example.org: a@example.org. See www.example.com");
+
+    assert_eq!(1, candidates.len());
+    assert_eq!(&LinkKind::Email, candidates[0].kind());
+    assert_eq!(21, candidates[0].trigger_index());
+}
+
+#[test]
+fn rejected() {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Url]);
+    let reason = |input| finder.explain(input)[0].reason();
+
+    assert_eq!(Some("nothing after the separator"), reason("http://"));
+    assert_eq!(Some("no valid end"), reason("http://."));
+    assert_eq!(Some("not a separator"), reason("Note: this"));
+    assert_eq!(
+        Some("scheme or host preceded by a digit or colon"),
+        reason("1abc://example.org")
+    );
+
+    finder.deny_url_schemes(&["javascript"]);
+    let candidates = finder.explain("javascript://example.org");
+    assert_eq!(Some("javascript"), candidates[0].scheme());
+    assert_eq!(Some("scheme not allowed"), candidates[0].reason());
+    assert!(candidates[0].link().is_none());
+}
+
+#[test]
+fn without_scheme() {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Url]);
+    finder.url_must_have_scheme(false);
+    finder.allowed_tlds(&["org"]);
+
+    let candidates = finder.explain("example.com example.org");
+    assert_eq!(2, candidates.len());
+    assert_eq!(Some("top-level domain not allowed"), candidates[0].reason());
+    assert_eq!(None, candidates[0].scheme());
+    assert_eq!("example.org", candidates[1].link().unwrap().as_str());
+}
+
+#[test]
+fn same_as_links() {
+    let finder = LinkFinder::new();
+    let input = "a@example.org, http://example.org/a.b and foo:bar http://";
+    let found: Vec<_> = finder
+        .explain(input)
+        .iter()
+        .filter_map(|candidate| candidate.link())
+        .map(|link| link.as_str())
+        .collect();
+    let links: Vec<_> = finder.links(input).map(|link| link.as_str()).collect();
+
+    assert_eq!(links, found);
+}
+
+#[test]
+fn other_kinds() {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Email]);
+    let candidates = finder.explain("a@b");
+
+    assert_eq!(1, candidates.len());
+    assert_eq!(&LinkKind::Email, candidates[0].kind());
+    assert_eq!(Some("no link found"), candidates[0].reason());
+    assert_eq!(None, candidates[0].scheme());
+}
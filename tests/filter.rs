@@ -0,0 +1,63 @@
+mod common;
+
+use crate::common::assert_linked_with;
+use linkify::{LinkFinder, LinkKind};
+
+#[test]
+fn filter_by_host() {
+    let mut finder = LinkFinder::new();
+    finder.filter(|link| link.host() == Some("example.org"));
+
+    assert_linked_with(
+        &finder,
+        "http://example.org/a, http://example.com/b and foo@example.org",
+        "|http://example.org/a|, http://example.com/b and |foo@example.org|",
+    );
+}
+
+#[test]
+fn filter_by_kind() {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Url, LinkKind::Hashtag]);
+    finder.filter(|link| link.kind() != &LinkKind::Hashtag || link.as_str().len() > 3);
+
+    assert_linked_with(
+        &finder,
+        "#ab #abc http://a.com",
+        "#ab |#abc| |http://a.com|",
+    );
+}
+
+#[test]
+fn rejected_links_are_skipped() {
+    let mut finder = LinkFinder::new();
+    finder.filter(|link| link.kind() != &LinkKind::Url);
+
+    // The email address in the rejected URL is not found
+    assert_linked_with(
+        &finder,
+        "http://example.com/?to=a@example.org b@example.org",
+        "http://example.com/?to=a@example.org |b@example.org|",
+    );
+}
+
+#[test]
+fn max_links_counts_accepted_links() {
+    let mut finder = LinkFinder::new();
+    finder.filter(|link| link.as_str().ends_with(".org"));
+    finder.max_links(Some(2));
+
+    let input = "http://a.com http://a.org http://b.com http://b.org http://c.org";
+    let links: Vec<_> = finder.links(input).map(|link| link.as_str()).collect();
+    assert_eq!(vec!["http://a.org", "http://b.org"], links);
+}
+
+#[test]
+fn explain() {
+    let mut finder = LinkFinder::new();
+    finder.filter(|link| link.host() != Some("example.com"));
+    let candidates = finder.explain("http://example.com/");
+
+    assert_eq!(1, candidates.len());
+    assert_eq!(Some("rejected by the filter"), candidates[0].reason());
+}
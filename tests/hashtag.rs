@@ -0,0 +1,92 @@
+mod common;
+
+use crate::common::assert_linked_with;
+use linkify::LinkFinder;
+use linkify::LinkKind;
+
+#[test]
+fn no_links() {
+    assert_not_linked("");
+    assert_not_linked("#");
+    assert_not_linked("# foo");
+    assert_not_linked("##");
+    assert_not_linked("#123");
+    assert_not_linked("#_");
+}
+
+#[test]
+fn simple() {
+    assert_linked("#rustlang", "|#rustlang|");
+    assert_linked("#rust_lang", "|#rust_lang|");
+    assert_linked("#2020vision", "|#2020vision|");
+    assert_linked("#1_a", "|#1_a|");
+}
+
+#[test]
+fn international() {
+    assert_linked("#über", "|#über|");
+    assert_linked("#日本語", "|#日本語|");
+}
+
+#[test]
+fn separation() {
+    assert_linked("I like #rustlang.", "I like |#rustlang|.");
+    assert_linked("#a, #b", "|#a|, |#b|");
+    assert_linked("#a\n#b", "|#a|\n|#b|");
+    assert_linked("#rust-lang", "|#rust|-lang");
+    assert_linked("#a#b", "|#a|#b");
+}
+
+#[test]
+fn inside_word() {
+    assert_not_linked("foo#bar");
+    assert_not_linked("(#bar)");
+    assert_not_linked("http://example.org/#bar");
+}
+
+#[test]
+fn kind() {
+    let finder = finder();
+    let links: Vec<_> = finder.links("#rustlang").collect();
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].kind(), &LinkKind::Hashtag);
+}
+
+#[test]
+fn not_found_by_default() {
+    let finder = LinkFinder::new();
+    assert_linked_with(&finder, "#rustlang", "#rustlang");
+}
+
+#[test]
+fn with_urls() {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Url, LinkKind::Hashtag]);
+    assert_linked_with(
+        &finder,
+        "http://example.org/ #foo http://example.org/#bar",
+        "|http://example.org/| |#foo| |http://example.org/#bar|",
+    );
+}
+
+#[test]
+fn max_length() {
+    let mut finder = finder();
+    finder.max_length(Some(4));
+    assert_linked_with(&finder, "#abc", "|#abc|");
+    assert_linked_with(&finder, "#abcd", "#abcd");
+}
+
+fn finder() -> LinkFinder {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Hashtag]);
+    finder
+}
+
+fn assert_not_linked(s: &str) {
+    assert_linked(s, s);
+}
+
+fn assert_linked(input: &str, expected: &str) {
+    assert_linked_with(&finder(), input, expected);
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use crate::common::assert_linked_with;
+use linkify::LinkFinder;
+use linkify::LinkKind;
+
+#[test]
+fn no_links() {
+    assert_not_linked("");
+    assert_not_linked("@");
+    assert_not_linked("@ foo");
+    assert_not_linked("@@");
+    assert_not_linked("@.foo");
+}
+
+#[test]
+fn simple() {
+    assert_linked("@user", "|@user|");
+    assert_linked("@user_name", "|@user_name|");
+    assert_linked("@User123", "|@User123|");
+}
+
+#[test]
+fn with_domain() {
+    assert_linked("@user@example.org", "|@user@example.org|");
+    assert_linked("@user@example.org.", "|@user@example.org|.");
+    assert_not_linked("@user@example");
+    assert_not_linked("@user@");
+}
+
+#[test]
+fn separation() {
+    assert_linked("Thanks @user!", "Thanks |@user|!");
+    assert_linked("@a, @b", "|@a|, |@b|");
+    assert_linked("@user's", "|@user|'s");
+    assert_linked("@user-name", "|@user|-name");
+}
+
+#[test]
+fn inside_word() {
+    assert_not_linked("foo@bar");
+    assert_not_linked("(@user)");
+}
+
+#[test]
+fn kind() {
+    let finder = finder();
+    let links: Vec<_> = finder.links("@user").collect();
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].kind(), &LinkKind::Mention);
+}
+
+#[test]
+fn not_found_by_default() {
+    let finder = LinkFinder::new();
+    assert_linked_with(&finder, "@user", "@user");
+}
+
+#[test]
+fn with_emails() {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Email, LinkKind::Mention]);
+
+    let links: Vec<_> = finder
+        .links("foo@example.org @user @user@example.org")
+        .collect();
+    assert_eq!(links.len(), 3);
+    assert_eq!(links[0].as_str(), "foo@example.org");
+    assert_eq!(links[0].kind(), &LinkKind::Email);
+    assert_eq!(links[1].as_str(), "@user");
+    assert_eq!(links[1].kind(), &LinkKind::Mention);
+    assert_eq!(links[2].as_str(), "@user@example.org");
+    assert_eq!(links[2].kind(), &LinkKind::Mention);
+}
+
+#[test]
+fn max_length() {
+    let mut finder = finder();
+    finder.max_length(Some(4));
+    assert_linked_with(&finder, "@abc", "|@abc|");
+    assert_linked_with(&finder, "@abcd", "@abcd");
+}
+
+fn finder() -> LinkFinder {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Mention]);
+    finder
+}
+
+fn assert_not_linked(s: &str) {
+    assert_linked(s, s);
+}
+
+fn assert_linked(input: &str, expected: &str) {
+    assert_linked_with(&finder(), input, expected);
+}
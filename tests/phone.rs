@@ -0,0 +1,111 @@
+mod common;
+
+use crate::common::assert_linked_with;
+use linkify::LinkFinder;
+use linkify::LinkKind;
+
+#[test]
+fn no_links() {
+    assert_not_linked("");
+    assert_not_linked("123");
+    assert_not_linked("555-010");
+    assert_not_linked("+1");
+    assert_not_linked("()");
+}
+
+#[test]
+fn international() {
+    assert_linked("+44 20 7946 0958", "|+44 20 7946 0958|");
+    assert_linked("+1-555-0100", "|+1-555-0100|");
+    assert_linked("+4420794609", "|+4420794609|");
+}
+
+#[test]
+fn national() {
+    assert_linked("(555) 012-3456", "|(555) 012-3456|");
+    assert_linked("555-012-3456", "|555-012-3456|");
+    assert_linked("555.012.3456", "|555.012.3456|");
+    assert_linked("020 7946 0958", "|020 7946 0958|");
+    assert_linked("5550123", "|5550123|");
+}
+
+#[test]
+fn separation() {
+    assert_linked("Call +44 20 7946 0958.", "Call |+44 20 7946 0958|.");
+    assert_linked(
+        "Call (555) 012-3456, please",
+        "Call |(555) 012-3456|, please",
+    );
+    assert_linked("(+44 20 7946 0958)", "(|+44 20 7946 0958|)");
+    assert_linked("Phone:5550123", "Phone:|5550123|");
+    assert_linked("555-0123 or", "|555-0123| or");
+    assert_linked("555 0123  456", "|555 0123|  456");
+    assert_linked("555 0123 -", "|555 0123| -");
+    assert_not_linked("555--0123456");
+    assert_linked("(555 012-3456", "(|555 012-3456|");
+}
+
+#[test]
+fn inside_other_text() {
+    assert_not_linked("order 12345678901234567890");
+    assert_not_linked("abc5550123");
+    assert_not_linked("5550123abc");
+    assert_not_linked("#5550123");
+    assert_not_linked("v1.5550123");
+    assert_not_linked("http://example.org/5550123");
+}
+
+#[test]
+fn other_numbers() {
+    assert_not_linked("192.168.100.200");
+    assert_not_linked("10.100.200.300");
+    assert_not_linked("2021-01-01");
+    assert_not_linked("2021.01.01");
+    assert_not_linked("1.2.3.4.5.6.7");
+    assert_not_linked("10.2.3.4567");
+    assert_not_linked("1 234 567");
+    assert_not_linked("12.34.56.78");
+
+    assert_linked("01.23.45.67.89", "|01.23.45.67.89|");
+    assert_linked("+1 234 567", "|+1 234 567|");
+    assert_linked("(555) 123.4567", "|(555) 123.4567|");
+}
+
+#[test]
+fn kind() {
+    let finder = finder();
+    let links: Vec<_> = finder.links("+44 20 7946 0958").collect();
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].kind(), &LinkKind::Phone);
+}
+
+#[test]
+fn not_found_by_default() {
+    let finder = LinkFinder::new();
+    assert_linked_with(&finder, "+44 20 7946 0958", "+44 20 7946 0958");
+}
+
+#[test]
+fn with_urls() {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Url, LinkKind::Phone]);
+    assert_linked_with(
+        &finder,
+        "http://example.org/5550123 5550123",
+        "|http://example.org/5550123| |5550123|",
+    );
+}
+
+fn finder() -> LinkFinder {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Phone]);
+    finder
+}
+
+fn assert_not_linked(s: &str) {
+    assert_linked(s, s);
+}
+
+fn assert_linked(input: &str, expected: &str) {
+    assert_linked_with(&finder(), input, expected);
+}
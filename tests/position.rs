@@ -0,0 +1,38 @@
+use linkify::LinkFinder;
+
+#[test]
+fn first_line() {
+    assert_positions("http://example.org", &[(1, 1)]);
+    assert_positions("See http://example.org", &[(1, 5)]);
+    assert_positions(
+        "See http://example.org and a@example.org",
+        &[(1, 5), (1, 28)],
+    );
+}
+
+#[test]
+fn line_breaks() {
+    assert_positions("\nhttp://example.org", &[(2, 1)]);
+    assert_positions("a\nb\n  http://example.org", &[(3, 3)]);
+    assert_positions("a\r\n\r\n  http://example.org", &[(3, 3)]);
+    assert_positions("a\rhttp://example.org", &[(2, 1)]);
+    assert_positions(
+        "http://example.org\nhttp://example.com x@example.org",
+        &[(1, 1), (2, 1), (2, 20)],
+    );
+}
+
+#[test]
+fn columns_in_chars() {
+    assert_positions("äöü 😀 http://example.org", &[(1, 7)]);
+    assert_positions("ä\nö http://example.org", &[(2, 3)]);
+}
+
+fn assert_positions(input: &str, expected: &[(usize, usize)]) {
+    let finder = LinkFinder::new();
+    let positions: Vec<_> = finder
+        .links_with_position(input)
+        .map(|(_, position)| position)
+        .collect();
+    assert_eq!(positions, expected, "positions in {:?}", input);
+}
@@ -0,0 +1,109 @@
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+
+use linkify::{LinkFinder, LinkKind};
+
+#[test]
+fn no_links() {
+    let finder = LinkFinder::new();
+    let result = finder.replace_all("foo bar", |_| unreachable!());
+    assert_eq!(result, "foo bar");
+    match result {
+        Cow::Borrowed(_) => {}
+        Cow::Owned(_) => panic!("expected borrowed result"),
+    }
+}
+
+#[test]
+fn empty() {
+    let finder = LinkFinder::new();
+    assert_eq!(finder.replace_all("", |_| unreachable!()), "");
+}
+
+#[test]
+fn links() {
+    let finder = LinkFinder::new();
+    let result = finder.replace_all(
+        "http://one.org/, foo@example.com and (http://two.org/)",
+        |link| format!("<{}>", link.as_str()),
+    );
+    assert_eq!(
+        result,
+        "<http://one.org/>, <foo@example.com> and (<http://two.org/>)"
+    );
+}
+
+#[test]
+fn only_link() {
+    let finder = LinkFinder::new();
+    let result = finder.replace_all("http://example.org/", |_| "x".to_string());
+    assert_eq!(result, "x");
+}
+
+#[test]
+fn by_kind() {
+    let finder = LinkFinder::new();
+    let result = finder.replace_all("foo@example.com http://example.org/", |link| {
+        match link.kind() {
+            LinkKind::Email => format!("mailto:{}", link.as_str()),
+            _ => link.as_str().to_string(),
+        }
+    });
+    assert_eq!(result, "mailto:foo@example.com http://example.org/");
+}
+
+#[test]
+fn multibyte() {
+    let finder = LinkFinder::new();
+    let result = finder.replace_all("ä http://üñîçøðé.com/ä ö", |_| "x".to_string());
+    assert_eq!(result, "ä x ö");
+}
+
+#[test]
+fn write_replaced() {
+    let finder = LinkFinder::new();
+    let mut out = String::new();
+    let input = "http://one.org/, foo@example.com and (http://two.org/)";
+    finder
+        .write_replaced(input, &mut out, |link, out| {
+            write!(out, "<{}>", link.as_str())
+        })
+        .unwrap();
+
+    let expected = finder.replace_all(input, |link| format!("<{}>", link.as_str()));
+    assert_eq!(out, expected);
+
+    out.clear();
+    finder
+        .write_replaced("foo bar", &mut out, |_, _| unreachable!())
+        .unwrap();
+    assert_eq!(out, "foo bar");
+}
+
+#[test]
+fn write_replaced_stops_on_fmt_failure() {
+    let finder = LinkFinder::new();
+    let mut out = String::new();
+    let result = finder.write_replaced("a http://example.org b", &mut out, |_, _| Err(fmt::Error));
+    assert!(result.is_err());
+    assert_eq!(out, "a ");
+}
+
+#[test]
+fn strip_links() {
+    let finder = LinkFinder::new();
+    assert_eq!(
+        finder.strip_links("Mail foo@example.com or see http://example.org/."),
+        "Mail  or see ."
+    );
+    assert_eq!(finder.strip_links("http://a.com http://b.com"), " ");
+    assert_eq!(finder.strip_links(""), "");
+    match finder.strip_links("no links") {
+        Cow::Borrowed(text) => assert_eq!(text, "no links"),
+        Cow::Owned(_) => panic!("expected borrowed result"),
+    }
+
+    let text = "Mail foo@example.com or see http://example.org/.";
+    let spans: String = finder.text_spans(text).collect();
+    assert_eq!(finder.strip_links(text), spans);
+}
@@ -0,0 +1,250 @@
+mod common;
+
+use std::ops::Range;
+
+use crate::common::assert_linked_with;
+use linkify::{LinkFinder, LinkKind, OverlapPolicy, Scanner};
+
+/// Finds issue references like `#123`.
+struct IssueScanner;
+
+impl Scanner for IssueScanner {
+    fn scan(&self, s: &str, trigger_index: usize) -> Option<Range<usize>> {
+        let digits = s[trigger_index + 1..]
+            .bytes()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        if digits == 0 {
+            return None;
+        }
+        Some(trigger_index..trigger_index + 1 + digits)
+    }
+}
+
+/// Finds references like `GH-123`, triggered by the `-`.
+struct PrefixedIssueScanner;
+
+impl Scanner for PrefixedIssueScanner {
+    fn scan(&self, s: &str, trigger_index: usize) -> Option<Range<usize>> {
+        let start = trigger_index.checked_sub(2)?;
+        if &s[start..trigger_index] != "GH" || s[..start].ends_with(char::is_alphanumeric) {
+            return None;
+        }
+        let digits = s[trigger_index + 1..]
+            .bytes()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        if digits == 0 {
+            return None;
+        }
+        Some(start..trigger_index + 1 + digits)
+    }
+}
+
+/// Finds everything from the start of the input up to the trigger, to test overlaps.
+struct PrefixScanner;
+
+impl Scanner for PrefixScanner {
+    fn scan(&self, _s: &str, trigger_index: usize) -> Option<Range<usize>> {
+        Some(0..trigger_index + 1)
+    }
+}
+
+/// Finds the word around the trigger, delimited by whitespace.
+struct WordScanner;
+
+impl Scanner for WordScanner {
+    fn scan(&self, s: &str, trigger_index: usize) -> Option<Range<usize>> {
+        let start = s[..trigger_index]
+            .rfind(char::is_whitespace)
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let end = s[trigger_index..]
+            .find(char::is_whitespace)
+            .map(|i| trigger_index + i)
+            .unwrap_or_else(|| s.len());
+        Some(start..end)
+    }
+}
+
+#[test]
+fn custom_scanner() {
+    let mut finder = LinkFinder::new();
+    finder.add_scanner("issue", b"#", Box::new(IssueScanner));
+
+    assert_linked_with(&finder, "Fixed in #123.", "Fixed in |#123|.");
+    assert_linked_with(&finder, "# not an issue", "# not an issue");
+    assert_linked_with(
+        &finder,
+        "See #1 and http://example.org/",
+        "See |#1| and |http://example.org/|",
+    );
+}
+
+#[test]
+fn custom_scanner_kind() {
+    let mut finder = LinkFinder::new();
+    finder.add_scanner("issue", b"#", Box::new(IssueScanner));
+
+    let links: Vec<_> = finder.links("#1 foo@example.org").collect();
+    assert_eq!(links.len(), 2);
+    assert_eq!(links[0].kind(), &LinkKind::Custom("issue"));
+    assert_eq!(links[0].start(), 0);
+    assert_eq!(links[0].end(), 2);
+    assert_eq!(links[1].kind(), &LinkKind::Email);
+}
+
+#[test]
+fn custom_scanner_with_restricted_kinds() {
+    let mut finder = LinkFinder::new();
+    finder.add_scanner("issue", b"#", Box::new(IssueScanner));
+    finder.kinds(&[]);
+
+    assert_linked_with(
+        &finder,
+        "#1 http://example.org/",
+        "|#1| http://example.org/",
+    );
+}
+
+#[test]
+fn custom_scanner_after_built_in() {
+    let mut finder = LinkFinder::new();
+    finder.add_scanner("prefix", b":", Box::new(PrefixScanner));
+
+    assert_linked_with(&finder, "http://example.org/", "|http://example.org/|");
+    assert_linked_with(&finder, "foo: bar", "|foo:| bar");
+}
+
+#[test]
+fn custom_scanner_no_overlap() {
+    let mut finder = LinkFinder::new();
+    finder.add_scanner("prefix", b"!", Box::new(PrefixScanner));
+
+    assert_linked_with(&finder, "a! b!", "|a!| b!");
+    assert_linked_with(
+        &finder,
+        "http://example.org/ b!",
+        "|http://example.org/| b!",
+    );
+}
+
+#[test]
+fn custom_scanner_custom_trigger() {
+    let mut finder = LinkFinder::new();
+    finder.add_scanner("issue", b"-", Box::new(PrefixedIssueScanner));
+
+    assert_linked_with(&finder, "Fixed in GH-123.", "Fixed in |GH-123|.");
+    assert_linked_with(&finder, "AGH-123 GH- GH-x", "AGH-123 GH- GH-x");
+}
+
+#[test]
+fn custom_scanner_multiple_triggers() {
+    let mut finder = LinkFinder::new();
+    finder.add_scanner("reference", b"#!", Box::new(IssueScanner));
+
+    assert_linked_with(
+        &finder,
+        "Fixed in #123 and !45.",
+        "Fixed in |#123| and |!45|.",
+    );
+    let kinds: Vec<_> = finder.links("#1 !2").map(|link| *link.kind()).collect();
+    assert_eq!(
+        kinds,
+        vec![LinkKind::Custom("reference"), LinkKind::Custom("reference")]
+    );
+}
+
+#[test]
+#[should_panic]
+fn custom_scanner_non_ascii_trigger() {
+    let mut finder = LinkFinder::new();
+    finder.add_scanner("invalid", &[b'#', 0xC3], Box::new(IssueScanner));
+}
+
+#[test]
+fn overlap_policy_same_trigger() {
+    let mut finder = LinkFinder::new();
+    finder.add_scanner("issue", b"#", Box::new(IssueScanner));
+    finder.add_scanner("word", b"#", Box::new(WordScanner));
+
+    assert_linked_with(&finder, "see #123abc", "see |#123|abc");
+
+    finder.overlap_policy(OverlapPolicy::LongestMatch);
+    assert_linked_with(&finder, "see #123abc", "see |#123abc|");
+    let link = finder.links("see #123abc").next().unwrap();
+    assert_eq!(&LinkKind::Custom("word"), link.kind());
+}
+
+#[test]
+fn overlap_policy_trigger_within_link() {
+    let mut finder = LinkFinder::new();
+    finder.add_scanner("issue", b"#", Box::new(IssueScanner));
+    finder.add_scanner("word", b"2", Box::new(WordScanner));
+
+    assert_linked_with(&finder, "see #123abc #4", "see |#123|abc |#4|");
+
+    finder.overlap_policy(OverlapPolicy::LongestMatch);
+    assert_linked_with(&finder, "see #123abc #4", "see |#123abc| |#4|");
+    assert_linked_with(&finder, "#1 #2 #3", "|#1| |#2| |#3|");
+}
+
+#[test]
+fn overlap_policy_built_in() {
+    let mut finder = LinkFinder::new();
+    finder.overlap_policy(OverlapPolicy::LongestMatch);
+
+    assert_linked_with(
+        &finder,
+        "mailto:a@example.org http://example.org/a@b.com",
+        "|mailto:a@example.org| |http://example.org/a@b.com|",
+    );
+}
+
+/// Finds nothing, but returns an empty range at the trigger.
+struct EmptyScanner;
+
+impl Scanner for EmptyScanner {
+    fn scan(&self, _s: &str, trigger_index: usize) -> Option<Range<usize>> {
+        Some(trigger_index..trigger_index)
+    }
+}
+
+#[test]
+fn empty_range_ignored() {
+    let mut finder = LinkFinder::new();
+    finder.add_scanner("empty", b"#", Box::new(EmptyScanner));
+    finder.add_scanner("issue", b"#", Box::new(IssueScanner));
+
+    assert_linked_with(&finder, "# #1 #", "# |#1| #");
+    assert_eq!(1, finder.spans("#").count());
+}
+
+/// Returns ranges that are out of bounds or not on char boundaries.
+struct InvalidScanner;
+
+impl Scanner for InvalidScanner {
+    fn scan(&self, s: &str, trigger_index: usize) -> Option<Range<usize>> {
+        if s[trigger_index + 1..].starts_with('ä') {
+            Some(trigger_index..trigger_index + 2)
+        } else {
+            Some(trigger_index..s.len() + 1)
+        }
+    }
+}
+
+#[test]
+fn invalid_range_ignored() {
+    let mut finder = LinkFinder::new();
+    finder.add_scanner("invalid", b"#", Box::new(InvalidScanner));
+    finder.add_scanner("issue", b"#", Box::new(IssueScanner));
+
+    assert_linked_with(&finder, "#ä #1 #", "#ä |#1| #");
+    assert_eq!(1, finder.spans("#").count());
+
+    let candidates = finder.explain("#");
+    assert_eq!(
+        Some("not a valid range of the text"),
+        candidates[0].reason()
+    );
+}
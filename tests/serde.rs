@@ -0,0 +1,33 @@
+#![cfg(feature = "serde")]
+
+use linkify::LinkFinder;
+use linkify::LinkKind;
+use linkify::OwnedLink;
+
+#[test]
+fn link_kind() {
+    assert_eq!(serde_json::to_string(&LinkKind::Url).unwrap(), "\"Url\"");
+    assert_eq!(
+        serde_json::from_str::<LinkKind>("\"Email\"").unwrap(),
+        LinkKind::Email
+    );
+    assert_eq!(
+        serde_json::to_string(&LinkKind::Custom("issue")).unwrap(),
+        "{\"Custom\":\"issue\"}"
+    );
+    assert!(serde_json::from_str::<LinkKind>("{\"Custom\":\"issue\"}").is_err());
+}
+
+#[test]
+fn owned_link() {
+    let finder = LinkFinder::new();
+    let link = finder.links("See http://example.org/").next().unwrap();
+    let owned = OwnedLink::from(link);
+
+    let json = serde_json::to_string(&owned).unwrap();
+    assert_eq!(
+        json,
+        "{\"text\":\"http://example.org/\",\"start\":4,\"end\":23,\"kind\":\"Url\"}"
+    );
+    assert_eq!(serde_json::from_str::<OwnedLink>(&json).unwrap(), owned);
+}
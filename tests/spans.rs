@@ -0,0 +1,141 @@
+use linkify::{LinkFinder, LinkKind, Token};
+
+#[test]
+fn kinds() {
+    let finder = LinkFinder::new();
+    let spans: Vec<_> = finder
+        .spans("Mail foo@example.com or see http://example.org/.")
+        .collect();
+
+    let kinds: Vec<_> = spans.iter().map(|span| span.kind()).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            None,
+            Some(&LinkKind::Email),
+            None,
+            Some(&LinkKind::Url),
+            None
+        ]
+    );
+    assert_eq!(spans[1].as_str(), "foo@example.com");
+    assert_eq!(spans[3].as_str(), "http://example.org/");
+}
+
+#[test]
+fn kinds_match_links() {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[
+        LinkKind::Url,
+        LinkKind::Email,
+        LinkKind::Hashtag,
+        LinkKind::Mention,
+    ]);
+    let input = "#tag @user foo@example.com http://example.org/";
+
+    let link_kinds: Vec<_> = finder.links(input).map(|link| *link.kind()).collect();
+    let span_kinds: Vec<_> = finder
+        .spans(input)
+        .filter_map(|span| span.kind().cloned())
+        .collect();
+    assert_eq!(link_kinds, span_kinds);
+    assert_eq!(
+        span_kinds,
+        vec![
+            LinkKind::Hashtag,
+            LinkKind::Mention,
+            LinkKind::Email,
+            LinkKind::Url
+        ]
+    );
+}
+
+#[test]
+fn no_empty_spans() {
+    let finder = LinkFinder::new();
+    let spans = |input| -> Vec<_> { finder.spans(input).map(|span| span.as_str()).collect() };
+
+    assert!(spans("").is_empty());
+    assert_eq!(spans("no links here"), vec!["no links here"]);
+    assert_eq!(spans("http://example.org"), vec!["http://example.org"]);
+    assert_eq!(
+        spans("a@example.com,b@example.com"),
+        vec!["a@example.com", ",", "b@example.com"]
+    );
+
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Hashtag]);
+    let spans: Vec<_> = finder.spans("#a#b").map(|span| span.as_str()).collect();
+    assert_eq!(spans, vec!["#a", "#b"]);
+}
+
+#[test]
+fn text_spans() {
+    let finder = LinkFinder::new();
+    let text = |input| finder.text_spans(input).collect::<Vec<_>>();
+
+    assert_eq!(
+        text("Mail foo@example.com or see http://example.org/."),
+        vec!["Mail ", " or see ", "."]
+    );
+    assert_eq!(text("http://a.com and http://b.com"), vec![" and "]);
+    assert_eq!(text("http://a.com"), Vec::<&str>::new());
+    assert_eq!(text("no links"), vec!["no links"]);
+    assert_eq!(text(""), Vec::<&str>::new());
+}
+
+#[test]
+fn ranges() {
+    let finder = LinkFinder::new();
+    let ranges = |input| finder.ranges(input).collect::<Vec<_>>();
+
+    assert_eq!(
+        ranges("Mail foo@example.com or see http://example.org/."),
+        vec![
+            (0..5, false),
+            (5..20, true),
+            (20..28, false),
+            (28..47, true),
+            (47..48, false)
+        ]
+    );
+    assert_eq!(
+        ranges("a@example.com,b@example.com"),
+        vec![(0..13, true), (13..14, false), (14..27, true)]
+    );
+    assert_eq!(ranges("no links"), vec![(0..8, false)]);
+    assert!(ranges("").is_empty());
+
+    let input = "Ünïcödé http://example.org/ ✓";
+    let spans: Vec<_> = finder.spans(input).collect();
+    let ranges = ranges(input);
+    assert_eq!(spans.len(), ranges.len());
+    for (span, (range, is_link)) in spans.iter().zip(ranges) {
+        assert_eq!(span.as_str(), &input[range]);
+        assert_eq!(span.kind().is_some(), is_link);
+    }
+}
+
+#[test]
+fn tokenize() {
+    let finder = LinkFinder::new();
+    let input = "Mail foo@example.com or see http://example.org/. http://a.com http://b.com";
+
+    let mut tokens = Vec::new();
+    finder.tokenize(input, |token| match token {
+        Token::Text(text) => tokens.push((text, None)),
+        Token::Link(link) => tokens.push((link.as_str(), Some(*link.kind()))),
+    });
+
+    let spans: Vec<_> = finder
+        .spans(input)
+        .map(|span| (span.as_str(), span.kind().cloned()))
+        .collect();
+    assert_eq!(tokens, spans);
+}
+
+#[test]
+fn tokenize_empty() {
+    let finder = LinkFinder::new();
+    finder.tokenize("", |_| panic!("no tokens expected"));
+}
@@ -0,0 +1,96 @@
+use linkify::{LinkFinder, LinkKind};
+
+#[test]
+fn split_links() {
+    let input = "See http://example.org/ and foo@example.com, or http://example.com.";
+    let finder = LinkFinder::new();
+    let expected: Vec<_> = finder
+        .links(input)
+        .map(|link| (link.as_str(), link.start(), link.end()))
+        .collect();
+
+    // Split at every possible position
+    for (i, _) in input.char_indices() {
+        assert_links(&finder, &[&input[..i], &input[i..]], &expected);
+    }
+}
+
+#[test]
+fn many_chunks() {
+    let input = "a http://example.org/a b\nc http://example.org/b\td foo@example.com";
+    let finder = LinkFinder::new();
+    let chunks: Vec<_> = input
+        .split_inclusive(|c: char| c == '/' || c == '.')
+        .collect();
+    assert_links(
+        &finder,
+        &chunks,
+        &[
+            ("http://example.org/a", 2, 22),
+            ("http://example.org/b", 27, 47),
+            ("foo@example.com", 50, 65),
+        ],
+    );
+}
+
+#[test]
+fn returned_when_complete() {
+    let finder = LinkFinder::new();
+    let mut stream = finder.stream();
+    assert!(stream.feed("http://example.org").is_empty());
+    assert!(stream.feed(" ").is_empty());
+    let links = stream.feed("more");
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].as_str(), "http://example.org");
+    assert!(stream.finish().is_empty());
+}
+
+#[test]
+fn link_across_whitespace() {
+    let finder = LinkFinder::new();
+    assert_links(
+        &finder,
+        &["a http://exa", "mple.org/ b c d"],
+        &[("http://example.org/", 2, 21)],
+    );
+    assert_links(
+        &finder,
+        &["a (http://example.org/", " b", " c"],
+        &[("http://example.org/", 3, 22)],
+    );
+}
+
+#[test]
+fn phone_with_spaces() {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Phone]);
+    assert_links(
+        &finder,
+        &["Call +44 20 7946 ", "0958 now"],
+        &[("+44 20 7946 0958", 5, 21)],
+    );
+}
+
+#[test]
+fn multibyte() {
+    let finder = LinkFinder::new();
+    assert_links(
+        &finder,
+        &["ä\n", "http://ä.org/ö", " ü http://ü.org/ä ö"],
+        &[("http://ä.org/ö", 3, 19), ("http://ü.org/ä", 23, 39)],
+    );
+}
+
+fn assert_links(finder: &LinkFinder, chunks: &[&str], expected: &[(&str, usize, usize)]) {
+    let mut stream = finder.stream();
+    let mut links = Vec::new();
+    for chunk in chunks {
+        links.extend(stream.feed(chunk));
+    }
+    links.extend(stream.finish());
+    let actual: Vec<_> = links
+        .iter()
+        .map(|link| (link.as_str(), link.start(), link.end()))
+        .collect();
+    assert_eq!(actual, expected, "links in {:?}", chunks);
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use crate::common::assert_linked_with;
+use linkify::LinkFinder;
+use linkify::LinkKind;
+
+#[test]
+fn no_links() {
+    assert_not_linked("tel:");
+    assert_not_linked("tel:abc");
+    assert_not_linked("tel: 555");
+    assert_not_linked("hotel:555");
+    assert_not_linked("tel:+");
+}
+
+#[test]
+fn simple() {
+    assert_linked("tel:+1-555-0100", "|tel:+1-555-0100|");
+    assert_linked("tel:5550100", "|tel:5550100|");
+    assert_linked("TEL:911", "|TEL:911|");
+    assert_linked("tel:555.0100", "|tel:555.0100|");
+    assert_linked("tel:+1 (555) 0100", "|tel:+1 (555) 0100|");
+}
+
+#[test]
+fn separation() {
+    assert_linked("Call tel:555-0100.", "Call |tel:555-0100|.");
+    assert_linked("tel:555-0100, please", "|tel:555-0100|, please");
+    assert_linked("(tel:555-0100)", "(|tel:555-0100|)");
+    assert_linked("tel:555 or", "|tel:555| or");
+    assert_linked("tel:555  0100", "|tel:555|  0100");
+    assert_linked("tel:555\n0100", "|tel:555|\n0100");
+    assert_linked("<a href=\"tel:555\">", "<a href=\"|tel:555|\">");
+    assert_linked("tel:555-", "|tel:555|-");
+}
+
+#[test]
+fn kind() {
+    let finder = finder();
+    let links: Vec<_> = finder.links("tel:+1-555-0100").collect();
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].kind(), &LinkKind::Tel);
+}
+
+#[test]
+fn not_found_by_default() {
+    let finder = LinkFinder::new();
+    assert_linked_with(&finder, "tel:+1-555-0100", "tel:+1-555-0100");
+}
+
+#[test]
+fn with_urls() {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Url, LinkKind::Tel]);
+    assert_linked_with(
+        &finder,
+        "http://example.org/ tel:555",
+        "|http://example.org/| |tel:555|",
+    );
+}
+
+#[test]
+fn max_length() {
+    let mut finder = finder();
+    finder.max_length(Some(8));
+    assert_linked_with(&finder, "tel:5550", "|tel:5550|");
+    assert_linked_with(&finder, "tel:55501", "tel:55501");
+}
+
+fn finder() -> LinkFinder {
+    let mut finder = LinkFinder::new();
+    finder.kinds(&[LinkKind::Tel]);
+    finder
+}
+
+fn assert_not_linked(s: &str) {
+    assert_linked(s, s);
+}
+
+fn assert_linked(input: &str, expected: &str) {
+    assert_linked_with(&finder(), input, expected);
+}
@@ -1,6 +1,18 @@
+use std::borrow::Cow;
+
+use linkify::BytesLinks;
 use linkify::Link;
 use linkify::LinkFinder;
 use linkify::LinkKind;
+use linkify::LinkStream;
+use linkify::Links;
+use linkify::LinksDetailed;
+use linkify::LinksWithContext;
+use linkify::LinksWithPosition;
+use linkify::OwnedLink;
+use linkify::Ranges;
+use linkify::Spans;
+use linkify::TextSpans;
 
 #[test]
 fn send_and_sync() {
@@ -9,6 +21,92 @@ fn send_and_sync() {
 
     check_send::<Link<'_>>();
     check_sync::<Link<'_>>();
+
+    check_send::<OwnedLink>();
+    check_sync::<OwnedLink>();
+}
+
+#[test]
+fn iterators_send_and_sync() {
+    check_send::<Links<'_>>();
+    check_sync::<Links<'_>>();
+
+    check_send::<Spans<'_>>();
+    check_sync::<Spans<'_>>();
+
+    check_send::<Ranges<'_>>();
+    check_sync::<Ranges<'_>>();
+    check_send::<TextSpans<'_>>();
+    check_sync::<TextSpans<'_>>();
+
+    check_send::<LinksWithContext<'_>>();
+    check_sync::<LinksWithContext<'_>>();
+
+    check_send::<LinksWithPosition<'_>>();
+    check_sync::<LinksWithPosition<'_>>();
+
+    check_send::<LinksDetailed<'_>>();
+    check_sync::<LinksDetailed<'_>>();
+
+    check_send::<BytesLinks<'_>>();
+    check_sync::<BytesLinks<'_>>();
+
+    check_send::<LinkStream<'_>>();
+    check_sync::<LinkStream<'_>>();
+}
+
+#[test]
+fn shared_between_threads() {
+    let finder = std::sync::Arc::new(LinkFinder::new());
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let finder = finder.clone();
+            std::thread::spawn(move || finder.count(&format!("http://example.org/{}", i)))
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(1, handle.join().unwrap());
+    }
+}
+
+#[test]
+fn links_outlive_finder() {
+    fn find_links(text: &str) -> Vec<Link<'_>> {
+        LinkFinder::new().links(text).collect()
+    }
+
+    let links = find_links("See http://example.org and foo@example.com");
+    assert_eq!(2, links.len());
+
+    let mut finder = LinkFinder::new();
+    finder.filter(|link| link.host() == Some("example.org"));
+    let spans = finder.spans("http://example.org http://example.com");
+    drop(finder);
+    assert_eq!(2, spans.count());
+}
+
+#[test]
+fn links_keep_options_of_finder() {
+    let text = "http://example.org foo@example.com";
+    let mut finder = LinkFinder::new();
+    let links = finder.links(text);
+    finder.kinds(&[LinkKind::Email]);
+    assert_eq!(2, links.count());
+    assert_eq!(1, finder.links(text).count());
+
+    finder.kinds(&[LinkKind::Url, LinkKind::Email]);
+    assert_eq!(2, finder.links(text).count());
+}
+
+#[test]
+fn static_finder() {
+    static FINDER: LinkFinder = LinkFinder::new();
+    let handles: Vec<_> = (0..4)
+        .map(|i| std::thread::spawn(move || FINDER.count(&format!("http://example.org/{}", i))))
+        .collect();
+    for handle in handles {
+        assert_eq!(1, handle.join().unwrap());
+    }
 }
 
 #[test]
@@ -24,6 +122,217 @@ fn equality() {
     assert_eq!(link.kind(), &LinkKind::Url);
 }
 
+#[test]
+fn offsets() {
+    let finder = LinkFinder::new();
+    let input = "ä http://üñîçøðé.com/ä ö";
+    let link = finder.links(input).next().unwrap();
+
+    assert_eq!(link.start(), 3);
+    assert_eq!(link.end(), 31);
+    assert_eq!(link.start_char(), 2);
+    assert_eq!(link.end_char(), 22);
+    let chars: String = input
+        .chars()
+        .skip(link.start_char())
+        .take(link.end_char() - link.start_char())
+        .collect();
+    assert_eq!(chars, link.as_str());
+}
+
+#[test]
+fn offsets_ascii() {
+    let finder = LinkFinder::new();
+    let link = finder.links("See http://example.org/").next().unwrap();
+
+    assert_eq!(link.start(), link.start_char());
+    assert_eq!(link.end(), link.end_char());
+}
+
+#[test]
+fn display_and_bytes() {
+    let finder = LinkFinder::new();
+    let link = finder.links("See http://example.org/ä.").next().unwrap();
+
+    assert_eq!("http://example.org/ä", link.to_string());
+    assert_eq!("<http://example.org/ä>", format!("<{}>", link));
+    assert_eq!("http://example.org/ä".as_bytes(), link.as_bytes());
+}
+
+#[test]
+fn owned_link() {
+    let finder = LinkFinder::new();
+    let owned: Vec<OwnedLink> = {
+        let input = String::from("See http://example.org/ and foo@example.com");
+        finder.links(&input).map(OwnedLink::from).collect()
+    };
+
+    assert_eq!(owned.len(), 2);
+    assert_eq!(owned[0].as_str(), "http://example.org/");
+    assert_eq!(owned[0].start(), 4);
+    assert_eq!(owned[0].end(), 23);
+    assert_eq!(owned[0].kind(), &LinkKind::Url);
+    assert_eq!(owned[1].as_str(), "foo@example.com");
+    assert_eq!(owned[1].kind(), &LinkKind::Email);
+}
+
+#[test]
+fn owned_link_other_thread() {
+    let finder = LinkFinder::new();
+    let link = {
+        let input = String::from("See http://example.org/.");
+        finder.links(&input).next().unwrap().into_owned()
+    };
+
+    let handle = std::thread::spawn(move || link.as_str().to_string());
+    assert_eq!(handle.join().unwrap(), "http://example.org/");
+}
+
+#[test]
+fn consuming_builders() {
+    let presets = [
+        ("default", LinkFinder::new()),
+        ("urls", LinkFinder::new().with_kinds(&[LinkKind::Url])),
+        (
+            "hosts",
+            LinkFinder::new()
+                .with_kinds(&[LinkKind::Url])
+                .without_url_scheme(),
+        ),
+        (
+            "secure",
+            LinkFinder::new()
+                .configure(|finder| finder.url_schemes(&["https"]).max_length(Some(30))),
+        ),
+    ];
+
+    let input = "example.org http://example.org https://example.org a@example.org";
+    let counts: Vec<_> = presets
+        .iter()
+        .map(|(name, finder)| (*name, finder.links(input).count()))
+        .collect();
+    assert_eq!(
+        counts,
+        vec![("default", 3), ("urls", 2), ("hosts", 3), ("secure", 2)]
+    );
+}
+
+#[test]
+fn first_link() {
+    let finder = LinkFinder::new();
+    let input = "a@example.org and http://example.org";
+    let first = finder.first_link(input).unwrap();
+    assert_eq!(finder.links(input).next().unwrap().as_str(), first.as_str());
+    assert_eq!(LinkKind::Email, *first.kind());
+    assert_eq!(0, first.start());
+
+    assert!(finder.first_link("").is_none());
+    assert!(finder.first_link("foo: bar").is_none());
+}
+
+#[test]
+fn link_kind_names() {
+    let names: Vec<_> = LinkKind::all().iter().map(LinkKind::as_str).collect();
+    assert_eq!(
+        vec!["url", "email", "tel", "phone", "hashtag", "mention"],
+        names
+    );
+    assert_eq!("issue", LinkKind::Custom("issue").as_str());
+}
+
+#[test]
+fn find_all() {
+    let finder = LinkFinder::new();
+    let input = "a@example.org, http://example.org and #foo";
+    let all: Vec<_> = finder.find_all(input).iter().map(Link::as_str).collect();
+    let links: Vec<_> = finder.links(input).map(|link| link.as_str()).collect();
+
+    assert_eq!(links, all);
+    assert!(finder.find_all("").is_empty());
+}
+
+#[test]
+fn unique_links() {
+    let finder = LinkFinder::new();
+    let input = "http://example.org/a, HTTP://Example.ORG/a, http://example.org/A, \
+                 a@Example.org, a@example.org, http://example.org/a";
+    let unique: Vec<_> = finder
+        .unique_links(input)
+        .iter()
+        .map(Link::as_str)
+        .collect();
+
+    assert_eq!(
+        vec![
+            "http://example.org/a",
+            "http://example.org/A",
+            "a@Example.org"
+        ],
+        unique
+    );
+    assert!(finder.unique_links("").is_empty());
+}
+
+#[test]
+fn count() {
+    let finder = LinkFinder::new();
+    let inputs = [
+        "",
+        "foo",
+        "a@example.org, http://example.org and b@example.org",
+        "http://example.org/a@b.org",
+    ];
+    for input in &inputs {
+        assert_eq!(finder.links(input).count(), finder.count(input));
+    }
+    assert_eq!(3, finder.count(inputs[2]));
+}
+
+#[test]
+fn count_with_stripped_query() {
+    let input = "http://example.org/?a=b http://example.com/?c=d";
+    let mut finder = LinkFinder::new();
+    finder.strip_query(true);
+    assert_eq!(2, finder.count(input));
+
+    // The filter gets the links without the query
+    finder.filter(|link| !link.as_str().contains('?') && link.host() == Some("example.org"));
+    assert_eq!(finder.links(input).count(), finder.count(input));
+    assert_eq!(1, finder.count(input));
+}
+
+#[test]
+fn max_links() {
+    let mut finder = LinkFinder::new();
+    let input = "http://a.com, b@example.org and http://c.com";
+    finder.max_links(Some(2));
+    let links: Vec<_> = finder.links(input).map(|link| link.as_str()).collect();
+    assert_eq!(vec!["http://a.com", "b@example.org"], links);
+    assert_eq!(2, finder.count(input));
+    assert_eq!(4, finder.spans(input).count());
+
+    finder.max_links(Some(0));
+    assert_eq!(0, finder.count(input));
+
+    finder.max_links(None);
+    assert_eq!(3, finder.count(input));
+}
+
+#[test]
+fn as_ref_input() {
+    let finder = LinkFinder::new();
+    let string = String::from("See http://example.org");
+    let cow: Cow<str> = Cow::Borrowed("See http://example.org");
+    let boxed: Box<str> = "See http://example.org".into();
+
+    assert_eq!(1, finder.links(&string).count());
+    assert_eq!(1, finder.links(&cow).count());
+    assert_eq!(1, finder.links(&boxed).count());
+    assert_eq!(2, finder.spans(&string).count());
+    assert_eq!("x", finder.first_link(&string).map(|_| "x").unwrap());
+    assert_eq!("See x", finder.replace_all(&string, |_| "x".to_string()));
+}
+
 fn check_send<T: Send>() {}
 
 fn check_sync<T: Sync>() {}